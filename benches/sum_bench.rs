@@ -1,44 +1,377 @@
+use std::fmt::Write as _;
+use std::fs;
 use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use criterion::{
+    AxisScale, BenchmarkId, Criterion, PlotConfiguration, Throughput, criterion_group,
+    criterion_main,
+};
 
 use sums::{
-    BLOCK, chunked_sum, expanded_fold_sum, fold_sum, for_sum, iter_sum, wide_sum_fold0,
-    wide_sum_fold1, wide_sum_fold2,
+    AlignedBuffer, Alignment, BLOCK, CacheSizes, Entry, Power, Prefetch, Scratch, Summer, Weighted,
+    fold_sum, fold_sum_scratch_len, kernel, kernels, prefetch_fold_sum_with,
 };
 
-const CASES: &[(usize, &str)] = &[
-    (BLOCK * BLOCK / 2 - 1, "N = BLOCK²/2 - 1"),
-    (BLOCK * BLOCK / 2, "N = BLOCK²/2"),
-    (BLOCK * BLOCK / 2 + 1, "N = BLOCK²/2 + 1"),
-    (200_000_000, "N = 200M"),
-];
+const LARGEST: usize = 200_000_000;
 
-const FUNCS: &[(&str, fn(&[f64]) -> f64)] = &[
-    ("for_sum", for_sum),
-    ("iter_sum", iter_sum),
-    ("fold_sum", fold_sum),
-    ("chunked_sum", chunked_sum),
-    ("wide_sum_fold0", wide_sum_fold0),
-    ("wide_sum_fold1", wide_sum_fold1),
-    ("wide_sum_fold2", wide_sum_fold2),
-    ("expanded_fold_sum", expanded_fold_sum),
+// Kernels compared across input alignments in `bench_alignment`.
+const ALIGNMENT_KERNELS: &[&str] = &[
+    "chunked_sum",
+    "fold_sum",
+    "wide_sum_fold1",
+    "prefetch_fold_sum",
 ];
 
+// Powers of two, the sizes around each cache level of this host, and odd
+// lengths that leave a remainder in each path: the 16 lanes, a BLOCK, the
+// first fold at BLOCK²/2 and the second one at BLOCK³/2.
+fn cases(caches: CacheSizes) -> Vec<usize> {
+    let mut cases: Vec<usize> = (4..)
+        .map(|p| 1 << p)
+        .take_while(|&n| n <= LARGEST)
+        .collect();
+    for bytes in [caches.l1, caches.l2, caches.l3] {
+        let n = bytes / size_of::<f64>();
+        cases.extend([n / 2, n, n * 2]);
+    }
+    cases.extend([
+        1,
+        15,
+        17,
+        31,
+        BLOCK - 1,
+        BLOCK + 15,
+        BLOCK * BLOCK / 2 - 1,
+        BLOCK * BLOCK / 2,
+        BLOCK * BLOCK / 2 + 1,
+        BLOCK * BLOCK / 2 + BLOCK - 1,
+        BLOCK * BLOCK * BLOCK / 2 + 1,
+        LARGEST,
+    ]);
+    cases.retain(|&n| n <= LARGEST);
+    cases.sort_unstable();
+    cases.dedup();
+    cases
+}
+
+// `len` values from `len` down to 1. The sweeps put them on huge pages, so
+// that alignment and TLB behaviour do not depend on the allocator.
+fn descending(len: usize, alignment: Alignment) -> AlignedBuffer {
+    let mut buffer = AlignedBuffer::new(len, alignment);
+    for (x, value) in buffer.iter_mut().zip((1..=len).rev()) {
+        *x = value as f64;
+    }
+    buffer
+}
+
+fn sweep_group<'a>(
+    c: &'a mut Criterion,
+    name: &str,
+) -> criterion::BenchmarkGroup<'a, criterion::measurement::WallTime> {
+    let mut group = c.benchmark_group(name);
+    group
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1))
+        .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    group
+}
+
+// Every registered kernel over one f64 slice: those returning an f64 or a
+// double-double, `p_norm` at p = 3, and the scratch kernels with a buffer
+// allocated up front.
 fn bench_sums(c: &mut Criterion) {
-    for &(n, label) in CASES {
-        let mut group = c.benchmark_group(label);
+    let caches = CacheSizes::detect();
+    let cases = cases(caches);
+    let data = descending(LARGEST, Alignment::HugePage);
+    let mut scratch = vec![0.0; fold_sum_scratch_len(LARGEST)];
+
+    let mut group = sweep_group(c, "sweep");
+    for &n in &cases {
         group.throughput(Throughput::Elements(n as u64));
+        let data = &data[..n];
 
-        let data: Vec<f64> = (1..=n).rev().map(|x| x as f64).collect();
+        for kernel in kernels::<f64, f64>() {
+            group.bench_with_input(BenchmarkId::new(kernel.name(), n), data, |b, data| {
+                b.iter(|| kernel.sum(black_box(data)))
+            });
+        }
+        for kernel in kernels::<f64, (f64, f64)>() {
+            group.bench_with_input(BenchmarkId::new(kernel.name(), n), data, |b, data| {
+                b.iter(|| kernel.sum(black_box(data)))
+            });
+        }
+        for kernel in kernels::<Power, f64>() {
+            group.bench_with_input(BenchmarkId::new(kernel.name(), n), data, |b, data| {
+                b.iter(|| kernel.sum((black_box(data), 3.0)))
+            });
+        }
+        for kernel in kernels::<Scratch, f64>() {
+            group.bench_with_input(BenchmarkId::new(kernel.name(), n), data, |b, data| {
+                b.iter(|| kernel.sum((black_box(data), &mut scratch)))
+            });
+        }
+    }
+    group.finish();
+
+    let names = kernels::<f64, f64>()
+        .map(|kernel| kernel.name())
+        .chain(kernels::<f64, (f64, f64)>().map(|kernel| kernel.name()))
+        .chain(kernels::<Power, f64>().map(|kernel| kernel.name()))
+        .chain(kernels::<Scratch, f64>().map(|kernel| kernel.name()));
+    plot_throughput("sweep", names, &cases, caches);
+}
 
-        for &(name, func) in FUNCS {
-            group.bench_function(name, |b| b.iter(|| func(black_box(&data))));
+fn bench_weighted_sums(c: &mut Criterion) {
+    let caches = CacheSizes::detect();
+    let cases = cases(caches);
+    let data = descending(LARGEST, Alignment::HugePage);
+    let mut weights = AlignedBuffer::new(LARGEST, Alignment::HugePage);
+    for (w, x) in weights.iter_mut().zip(1..=LARGEST) {
+        *w = 1.0 / x as f64;
+    }
+
+    let mut group = sweep_group(c, "weighted sweep");
+    for &n in &cases {
+        group.throughput(Throughput::Elements(n as u64));
+        let input = (&data[..n], &weights[..n]);
+
+        for kernel in kernels::<Weighted, f64>() {
+            group.bench_with_input(
+                BenchmarkId::new(kernel.name(), n),
+                &input,
+                |b, &(data, weights)| b.iter(|| kernel.sum((black_box(data), black_box(weights)))),
+            );
         }
+    }
+    group.finish();
+
+    let names = kernels::<Weighted, f64>().map(|kernel| kernel.name());
+    plot_throughput("weighted sweep", names, &cases, caches);
+}
+
+// `prefetch_fold_sum` against `fold_sum` where it is meant to help: inputs
+// four times the last-level cache and the largest case, for a range of
+// prefetch distances and both hints. Distance 0 prefetches the lines being
+// loaded, so it measures the interleaving alone.
+fn bench_prefetch(c: &mut Criterion) {
+    let caches = CacheSizes::detect();
+    let mut cases = vec![caches.l3 * 4 / size_of::<f64>(), LARGEST];
+    cases.retain(|&n| n <= LARGEST);
+    cases.dedup();
+    let data = descending(LARGEST, Alignment::HugePage);
+
+    let mut group = sweep_group(c, "prefetch");
+    for &n in &cases {
+        group.throughput(Throughput::Bytes((n * size_of::<f64>()) as u64));
+        let data = &data[..n];
 
-        group.finish();
+        group.bench_with_input(BenchmarkId::new("fold_sum", n), data, |b, data| {
+            b.iter(|| fold_sum(black_box(data)))
+        });
+        for non_temporal in [false, true] {
+            for distance in [0, 256, 512, 1024, 2048, 4096] {
+                let prefetch = Prefetch {
+                    distance,
+                    non_temporal,
+                };
+                let hint = if non_temporal { "nta" } else { "t0" };
+                let name = format!("prefetch_fold_sum {hint} {distance}");
+                group.bench_with_input(BenchmarkId::new(name, n), data, |b, data| {
+                    b.iter(|| prefetch_fold_sum_with(black_box(data), prefetch))
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+// The same kernels over the same values at different starting addresses: as
+// the allocator happens to place a `Vec`, on a cache line, 8 and 32 bytes
+// past one (so that some vector loads split a line), on a page and on huge
+// pages. Each layout is allocated in turn to bound the memory used.
+fn bench_alignment(c: &mut Criterion) {
+    let caches = CacheSizes::detect();
+    let mut cases: Vec<usize> = [caches.l1 / 2, caches.l2 / 2, caches.l3 * 4]
+        .map(|bytes| (bytes / size_of::<f64>()).min(LARGEST))
+        .to_vec();
+    cases.dedup();
+    let kernels: Vec<_> = ALIGNMENT_KERNELS
+        .iter()
+        .filter_map(|&name| kernel(name)?.downcast::<f64, f64>())
+        .collect();
+
+    let mut group = sweep_group(c, "alignment");
+    for &n in &cases {
+        group.throughput(Throughput::Elements(n as u64));
+        let mut bench = |layout: &str, data: &[f64]| {
+            for kernel in &kernels {
+                let name = format!("{} {layout}", kernel.name());
+                group.bench_with_input(BenchmarkId::new(name, n), data, |b, data| {
+                    b.iter(|| kernel.sum(black_box(data)))
+                });
+            }
+        };
+
+        let vec: Vec<f64> = descending(n, Alignment::CacheLine).to_vec();
+        bench("vec", &vec);
+        drop(vec);
+
+        // n values starting 0, 8 and 32 bytes past a cache line.
+        let line = descending(n + 4, Alignment::CacheLine);
+        bench("64", &line[..n]);
+        bench("64+8", &line[1..n + 1]);
+        bench("64+32", &line[4..]);
+        drop(line);
+
+        bench("page", &descending(n, Alignment::Page));
+        bench("huge page", &descending(n, Alignment::HugePage));
+    }
+    group.finish();
+}
+
+// Criterion's summary plots time against size; this draws elements per second
+// against size from its estimates instead, with the cache sizes marked, as
+// `<criterion dir>/<group>/throughput.svg`.
+fn plot_throughput<'a>(
+    group: &str,
+    names: impl Iterator<Item = &'a str>,
+    cases: &[usize],
+    caches: CacheSizes,
+) {
+    let dir = criterion_dir().join(group);
+    let series: Vec<(&str, Vec<(f64, f64)>)> = names
+        .map(|name| {
+            let points = cases
+                .iter()
+                .filter_map(|&n| {
+                    let path = dir
+                        .join(name)
+                        .join(n.to_string())
+                        .join("new/estimates.json");
+                    let nanos = mean_estimate(&fs::read_to_string(path).ok()?)?;
+                    Some(((n as f64).log2(), n as f64 / nanos))
+                })
+                .collect();
+            (name, points)
+        })
+        .filter(|(_, points): &(_, Vec<_>)| !points.is_empty())
+        .collect();
+    if series.is_empty() {
+        return;
+    }
+
+    let (width, height, margin) = (960.0, 540.0, 60.0);
+    let x_max = series
+        .iter()
+        .flat_map(|(_, points)| points.iter().map(|&(x, _)| x))
+        .fold(1.0, f64::max);
+    let y_max = series
+        .iter()
+        .flat_map(|(_, points)| points.iter().map(|&(_, y)| y))
+        .fold(0.0, f64::max);
+    let x = |log2_n: f64| margin + log2_n / x_max * (width - 2.0 * margin);
+    let y = |rate: f64| height - margin - rate / y_max * (height - 2.0 * margin);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="20" text-anchor="middle">{group}: elements per ns against N</text>"#,
+        width / 2.0
+    );
+    let _ = writeln!(
+        svg,
+        r#"<path d="M{margin},{margin} V{} H{}" fill="none" stroke="black"/>"#,
+        height - margin,
+        width - margin
+    );
+    for p in (0..=x_max as u32).step_by(4) {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">2^{p}</text>"#,
+            x(p as f64),
+            height - margin + 20.0
+        );
+    }
+    for i in 0..=4 {
+        let rate = y_max * i as f64 / 4.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{:.1}" text-anchor="end">{rate:.1}</text>"#,
+            margin - 5.0,
+            y(rate)
+        );
+    }
+    for (label, bytes) in [("L1", caches.l1), ("L2", caches.l2), ("L3", caches.l3)] {
+        let cache_x = x(((bytes / size_of::<f64>()) as f64).log2());
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{cache_x:.1}" y1="{}" x2="{cache_x:.1}" y2="{}" stroke="#999" stroke-dasharray="4"/><text x="{cache_x:.1}" y="{}" text-anchor="middle">{label}</text>"##,
+            margin,
+            height - margin,
+            margin - 5.0
+        );
     }
+
+    const COLORS: &[&str] = &[
+        "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+        "#bcbd22", "#17becf",
+    ];
+    for (i, (name, points)) in series.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        let dash = if i < COLORS.len() { "" } else { "6 3" };
+        let points: Vec<String> = points
+            .iter()
+            .map(|&(log2_n, rate)| format!("{:.1},{:.1}", x(log2_n), y(rate)))
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{color}" stroke-dasharray="{dash}"/>"#,
+            points.join(" ")
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" fill="{color}">{name}</text>"#,
+            margin + 10.0,
+            margin + 15.0 * (i + 1) as f64
+        );
+    }
+    svg.push_str("</svg>\n");
+
+    let path = dir.join("throughput.svg");
+    if fs::write(&path, svg).is_ok() {
+        println!("throughput plot: {}", path.display());
+    }
+}
+
+// Criterion's own lookup, without the `cargo metadata` step.
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return home.into();
+    }
+    let target = std::env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+    PathBuf::from(target).join("criterion")
+}
+
+// The point estimate of the mean in nanoseconds, from `{"mean":{...,"point_estimate":X,...`.
+fn mean_estimate(estimates: &str) -> Option<f64> {
+    let mean = &estimates[estimates.find("\"mean\"")?..];
+    let value = &mean[mean.find("\"point_estimate\":")? + "\"point_estimate\":".len()..];
+    let end = value.find([',', '}'])?;
+    value[..end].trim().parse().ok()
 }
 
-criterion_group!(benches, bench_sums);
+criterion_group!(
+    benches,
+    bench_sums,
+    bench_weighted_sums,
+    bench_prefetch,
+    bench_alignment
+);
 criterion_main!(benches);
@@ -0,0 +1,112 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{AtScale, Decimal, Entry, Summer, kernels};
+use sums_fuzz::f64s;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&first, rest)) = data.split_first() else {
+        return;
+    };
+    // Mostly the scales an i128 can hold digits for, sometimes past the
+    // largest one that any f64 fits at.
+    let scale = match first {
+        0..200 => first as u32 % 40,
+        _ => (first as u32 - 200) * 7,
+    };
+
+    let values = f64s(rest);
+    for &x in &values {
+        from_f64_like_format(x, scale);
+    }
+    let rounded = values.iter().try_fold(Decimal::new(0, scale), |sum, &x| {
+        sum.checked_add(Decimal::from_f64(x, scale)?)
+    });
+    for kernel in kernels::<AtScale, Option<Decimal>>() {
+        let sum = kernel.sum((&values, scale));
+        assert_eq!(sum, rounded, "{} at scale {scale}", kernel.name());
+        assert!(
+            sum.is_none_or(|sum| sum.scale == scale),
+            "{}: {sum:?}",
+            kernel.name()
+        );
+    }
+
+    // 8 bytes of units and one of scale per decimal.
+    let decimals: Vec<Decimal> = rest
+        .as_chunks::<9>()
+        .0
+        .iter()
+        .map(|chunk| {
+            let units = i64::from_le_bytes(chunk[..8].try_into().unwrap());
+            Decimal::new(units as i128, chunk[8] as u32 % 19)
+        })
+        .collect();
+    for kernel in kernels::<Decimal, Option<Decimal>>() {
+        check_exact(kernel.name(), &decimals, kernel.sum(&decimals));
+    }
+});
+
+// The rounded decimal is what formatting the f64 to `scale` places prints,
+// which also rounds the exact binary value half to even; `None` exactly when
+// those digits do not fit in an i128.
+fn from_f64_like_format(x: f64, scale: u32) {
+    let decimal = Decimal::from_f64(x, scale);
+    if !x.is_finite() {
+        assert_eq!(decimal, None, "{x:e} at scale {scale}");
+        return;
+    }
+
+    let formatted = format!("{x:.0$}", scale as usize);
+    let digits = formatted.trim_start_matches('-').replace('.', "");
+    let Ok(magnitude) = digits.parse::<i128>() else {
+        assert_eq!(decimal, None, "{x:e} at scale {scale}: {formatted}");
+        return;
+    };
+    let Some(decimal) = decimal else {
+        panic!("{x:e} at scale {scale}: None instead of {formatted}");
+    };
+    let units = if x < 0.0 { -magnitude } else { magnitude };
+    assert_eq!(
+        (decimal.units, decimal.scale),
+        (units, scale),
+        "{x:e}: {formatted}"
+    );
+    // Formatting keeps the sign of a negative value that rounds to zero.
+    let expected = if magnitude == 0 {
+        formatted.trim_start_matches('-')
+    } else {
+        &formatted
+    };
+    assert_eq!(decimal.to_string(), expected, "{x:e} at scale {scale}");
+}
+
+// The exact total at the largest scale, and `None` only once a partial sum
+// leaves the i128. The reference adds every value, rescaled, into signed high
+// bits and an unsigned low half that keeps the carries.
+fn check_exact(name: &str, decimals: &[Decimal], sum: Option<Decimal>) {
+    let scale = decimals.iter().map(|d| d.scale).max().unwrap_or(0);
+    let (mut high, mut low) = (0i128, 0u128);
+    let mut overflowed = false;
+    for decimal in decimals {
+        // |units| < 2^63 and 10^18 < 2^60, so the rescaled value fits.
+        let units = decimal.units * 10i128.pow(scale - decimal.scale);
+        high += units >> 64;
+        low += units as u128 & u64::MAX as u128;
+        high += (low >> 64) as i128;
+        low &= u64::MAX as u128;
+        overflowed |= i64::try_from(high).is_err();
+    }
+    let total = i64::try_from(high)
+        .ok()
+        .map(|high| ((high as i128) << 64) | low as i128);
+
+    match sum {
+        Some(sum) => assert_eq!(
+            (Some(sum.units), sum.scale),
+            (total, scale),
+            "{name} of {decimals:?}"
+        ),
+        None => assert!(overflowed, "{name}: None for {total:?} at scale {scale}"),
+    }
+}
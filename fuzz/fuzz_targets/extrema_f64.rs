@@ -0,0 +1,76 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Entry, NanPolicy, Summer, WithNanPolicy, kernels};
+use sums_fuzz::f64s;
+
+fuzz_target!(|data: &[u8]| {
+    let values = f64s(data);
+    for nan in [NanPolicy::Propagate, NanPolicy::Ignore] {
+        let (argmin, argmax) = (
+            first(&values, nan, below),
+            first(&values, nan, |a, b| below(b, a)),
+        );
+        let at = |index: Option<usize>| index.map(|i| values[i]);
+
+        for kernel in kernels::<WithNanPolicy, Option<f64>>() {
+            let expected = match kernel.name() {
+                "min" => at(argmin),
+                "max" => at(argmax),
+                name => panic!("{name}: no reference"),
+            };
+            same(kernel.name(), nan, kernel.sum((&values, nan)), expected);
+        }
+        for kernel in kernels::<WithNanPolicy, Option<(f64, f64)>>() {
+            assert_eq!(kernel.name(), "min_max", "{}: no reference", kernel.name());
+            let (min, max) = kernel.sum((&values, nan)).unzip();
+            same("min_max min", nan, min, at(argmin));
+            same("min_max max", nan, max, at(argmax));
+        }
+        for kernel in kernels::<WithNanPolicy, Option<usize>>() {
+            let expected = match kernel.name() {
+                "argmin" => argmin,
+                "argmax" => argmax,
+                name => panic!("{name}: no reference"),
+            };
+            let index = kernel.sum((&values, nan));
+            assert_eq!(index, expected, "{} {nan:?}", kernel.name());
+        }
+    }
+});
+
+// a < b with -0.0 below +0.0, as the kernels order values.
+fn below(a: f64, b: f64) -> bool {
+    a < b || (a == b && a.is_sign_negative() && b.is_sign_positive())
+}
+
+// The first index of the extreme value under `before`. Under `Propagate` it
+// is the first NaN if there is one; under `Ignore` NaN is skipped, and only
+// NaN input gives `None`.
+fn first(values: &[f64], nan: NanPolicy, before: fn(f64, f64) -> bool) -> Option<usize> {
+    if nan == NanPolicy::Propagate
+        && let Some(i) = values.iter().position(|x| x.is_nan())
+    {
+        return Some(i);
+    }
+    let mut extreme: Option<usize> = None;
+    for (i, &x) in values.iter().enumerate() {
+        if !x.is_nan() && extreme.is_none_or(|e| before(x, values[e])) {
+            extreme = Some(i);
+        }
+    }
+    extreme
+}
+
+// Under `Propagate` the value is the one at the index bit for bit, or any
+// NaN; under `Ignore` either zero may come back.
+fn same(name: &str, nan: NanPolicy, value: Option<f64>, expected: Option<f64>) {
+    let same = match (value, expected) {
+        (Some(a), Some(b)) if nan == NanPolicy::Propagate => {
+            a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+        }
+        (Some(a), Some(b)) => a == b,
+        (a, b) => a.is_none() && b.is_none(),
+    };
+    assert!(same, "{name} {nan:?}: {value:?} instead of {expected:?}");
+}
@@ -0,0 +1,33 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Accuracy, BLOCK, Entry, Operation, Summer, kernels};
+use sums_fuzz::{F64_LIMIT, Reference, f64s, same_as_slice_kernels};
+
+const U: f64 = f64::EPSILON / 2.0;
+
+// Inputs within libFuzzer's default -max_len of 4096 bytes stay far below the
+// BLOCK * BLOCK / 2 values where the blocked kernels start to fold, so this
+// target repeats the values past it, by a number that depends on the first
+// byte, and checks the fold path. It runs about a hundred inputs a second, so
+// it is separate from `kernels_f64`.
+fuzz_target!(|data: &[u8]| {
+    let values = f64s(data);
+    let Some(&first) = data.first() else {
+        return;
+    };
+    if values.is_empty() {
+        return;
+    }
+
+    let len = BLOCK * BLOCK / 2 + first as usize * 67;
+    let tiled: Vec<f64> = values.iter().copied().cycle().take(len).collect();
+    let reference = Reference::tiled(&values, len);
+    for kernel in kernels::<f64, f64>() {
+        if kernel.operation() == Operation::Sum && kernel.accuracy() == Accuracy::Blocked {
+            let sum = kernel.sum(&tiled);
+            reference.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum);
+        }
+    }
+    same_as_slice_kernels(&tiled, first as usize * 1031);
+});
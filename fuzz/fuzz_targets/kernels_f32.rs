@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Entry, Interleaved, Summer, kernels};
+use sums_fuzz::{F32_LIMIT, Reference, f32s};
+
+const U: f64 = f32::EPSILON as f64 / 2.0;
+
+fuzz_target!(|data: &[u8]| {
+    let values = f32s(data);
+
+    // Interleaved complex values: even indices are real parts.
+    let pairs = &values[..values.len() / 2 * 2];
+    let re = Reference::new(pairs.iter().step_by(2).map(|&x| x as f64));
+    let im = Reference::new(pairs.iter().skip(1).step_by(2).map(|&x| x as f64));
+    for kernel in kernels::<Interleaved<f32>, (f32, f32)>() {
+        let (name, accuracy) = (kernel.name(), kernel.accuracy());
+        let (sum_re, sum_im) = kernel.sum(pairs);
+        re.check(name, accuracy, U, F32_LIMIT as f64, sum_re as f64);
+        im.check(name, accuracy, U, F32_LIMIT as f64, sum_im as f64);
+    }
+});
@@ -0,0 +1,72 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{
+    Entry, Gathered, Interleaved, Operation, Strided, Summer, dd_sum, kernels, reproducible_sum,
+};
+use sums_fuzz::{F64_LIMIT, Reference, f64s, same, same_as_slice_kernels};
+
+const U: f64 = f64::EPSILON / 2.0;
+
+fuzz_target!(|data: &[u8]| {
+    let values = f64s(data);
+    let first = data.first().map_or(0, |&b| b as usize);
+    let reference = Reference::new(values.iter().copied());
+    for kernel in kernels::<f64, f64>().filter(|kernel| kernel.operation() == Operation::Sum) {
+        let sum = kernel.sum(&values);
+        reference.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum);
+    }
+    // Every digit of the exact sum, so it parses to the correctly rounded sum.
+    for kernel in kernels::<f64, String>() {
+        let sum = kernel.sum(&values);
+        let parsed = sum
+            .parse()
+            .unwrap_or_else(|_| panic!("{}: '{sum}' is not a number", kernel.name()));
+        reference.check(kernel.name(), kernel.accuracy(), U, f64::INFINITY, parsed);
+    }
+
+    // The exact bins do not depend on the order.
+    let reversed: Vec<f64> = values.iter().rev().copied().collect();
+    same(
+        "reproducible_sum reversed",
+        reproducible_sum(&reversed),
+        reproducible_sum(&values),
+    );
+
+    same_as_slice_kernels(&values, first);
+
+    let (hi, lo) = dd_sum(&values);
+    same("dd_sum", hi, hi + lo);
+
+    // Strides up to 16, and one so large that 16 of them overflow.
+    let stride = if first == 255 {
+        usize::MAX
+    } else {
+        1 + first % 16
+    };
+    let strided = Reference::new(values.iter().copied().step_by(stride));
+    for kernel in kernels::<Strided, f64>() {
+        let sum = kernel.sum((&values, stride));
+        strided.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum);
+    }
+
+    // Indices from the input bytes, repeated and in any order.
+    if !values.is_empty() {
+        let indices: Vec<usize> = data.iter().map(|&b| b as usize % values.len()).collect();
+        let gathered = Reference::new(indices.iter().map(|&i| values[i]));
+        for kernel in kernels::<Gathered, f64>() {
+            let sum = kernel.sum((&values, &indices));
+            gathered.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum);
+        }
+    }
+
+    // Interleaved complex values: even indices are real parts.
+    let pairs = &values[..values.len() / 2 * 2];
+    let re = Reference::new(pairs.iter().copied().step_by(2));
+    let im = Reference::new(pairs.iter().copied().skip(1).step_by(2));
+    for kernel in kernels::<Interleaved<f64>, (f64, f64)>() {
+        let (sum_re, sum_im) = kernel.sum(pairs);
+        re.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum_re);
+        im.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum_im);
+    }
+});
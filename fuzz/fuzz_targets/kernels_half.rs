@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Entry, Summer, bf16_to_f32, f16_to_f32, kernels};
+use sums_fuzz::{F32_LIMIT, F64_LIMIT, Reference, u16s};
+
+fuzz_target!(|data: &[u8]| {
+    let bits = u16s(data);
+    let widen = |convert: fn(u16) -> f32| bits.iter().map(move |&x| convert(x) as f64);
+    let f16 = Reference::new(widen(f16_to_f32));
+    let bf16 = Reference::new(widen(bf16_to_f32));
+    let reference = |name: &str| {
+        if name.starts_with("bf16") {
+            &bf16
+        } else {
+            &f16
+        }
+    };
+
+    for kernel in kernels::<u16, f32>() {
+        let u = f32::EPSILON as f64 / 2.0;
+        let sum = kernel.sum(&bits) as f64;
+        reference(kernel.name()).check(kernel.name(), kernel.accuracy(), u, F32_LIMIT as f64, sum);
+    }
+    for kernel in kernels::<u16, f64>() {
+        let u = f64::EPSILON / 2.0;
+        let sum = kernel.sum(&bits);
+        reference(kernel.name()).check(kernel.name(), kernel.accuracy(), u, F64_LIMIT, sum);
+    }
+});
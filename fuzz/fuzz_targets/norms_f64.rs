@@ -0,0 +1,109 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Entry, Operation, Power, Summer, kernels};
+use sums_fuzz::{F64_LIMIT, Reference, f64s, gamma, same};
+
+const U: f64 = f64::EPSILON / 2.0;
+
+// The first byte picks the p of `p_norm`; 1, 2 and infinity must give the
+// bits of the dedicated kernels.
+const P: [f64; 8] = [1.0, 2.0, f64::INFINITY, 0.5, 1.5, 3.0, 4.0, 10.0];
+
+fuzz_target!(|data: &[u8]| {
+    let values = f64s(data);
+    let p = P[data.first().map_or(0, |&b| b as usize % P.len())];
+
+    let linf = values.iter().fold(0.0, |s: f64, &x| {
+        if s.is_nan() || x.is_nan() {
+            f64::NAN
+        } else {
+            s.max(x.abs())
+        }
+    });
+    let mut norms = Vec::new();
+    for kernel in kernels::<f64, f64>().filter(|kernel| kernel.operation() == Operation::Norm) {
+        let (name, norm) = (kernel.name(), kernel.sum(&values));
+        match name {
+            "l1_norm" => Reference::new(values.iter().map(|x| x.abs())).check(
+                name,
+                kernel.accuracy(),
+                U,
+                F64_LIMIT,
+                norm,
+            ),
+            "l2_norm" => check_l2(&values, linf, norm),
+            "linf_norm" => same(name, norm, linf),
+            _ => panic!("{name}: no reference"),
+        }
+        norms.push((name, norm));
+    }
+
+    for kernel in kernels::<Power, f64>() {
+        let norm = kernel.sum((&values, p));
+        let dedicated = match p {
+            1.0 => Some("l1_norm"),
+            2.0 => Some("l2_norm"),
+            f64::INFINITY => Some("linf_norm"),
+            _ => None,
+        };
+        match norms.iter().find(|&&(name, _)| Some(name) == dedicated) {
+            Some(&(name, expected)) => same(&format!("{} {name}", kernel.name()), norm, expected),
+            None => check_p(kernel.name(), &values, linf, p, norm),
+        }
+    }
+});
+
+// The squares of the values scaled by the power of two at or below the
+// largest |x| are summed exactly as their rounded values and FMA errors, so
+// the reference is sqrt(sum) * scale with a few roundings. Squares that
+// underflow are below 2^-1022 of the largest one unless all values are
+// subnormal, and those are scaled by 2^1022.
+fn check_l2(values: &[f64], linf: f64, norm: f64) {
+    if linf == 0.0 || !linf.is_finite() {
+        same("l2_norm", norm, linf);
+        return;
+    }
+
+    let exponent = (linf.log2().floor() as i32).clamp(-1022, 1023);
+    let squares = values.iter().flat_map(|&x| {
+        let x = x * 2f64.powi(-exponent);
+        let square = x * x;
+        [square, x.mul_add(x, -square)]
+    });
+    let sum = Reference::new(squares).exact();
+    let expected = sum.sqrt() * 2f64.powi(exponent);
+
+    // Half the relative error of the sum, the square root, and the roundings
+    // of the reference; subnormal norms lose up to an ulp of 2^-1074.
+    let depth = values.len().div_ceil(8) + 32;
+    let bound = (gamma(depth, U) / 2.0 + 8.0 * U) * expected + f64::from_bits(1);
+    if expected > f64::MAX * (1.0 - 8.0 * U) {
+        assert!(
+            norm.is_infinite() || norm >= f64::MAX * (1.0 - 8.0 * U),
+            "l2_norm: {norm:e} instead of about {expected:e}"
+        );
+        return;
+    }
+    assert!(
+        (norm - expected).abs() <= bound,
+        "l2_norm: {norm:e} differs from {expected:e} by more than {bound:e}"
+    );
+}
+
+// Any p: the |x| / max to the p, summed naively. `powf` is only faithful to
+// a few ulps, so the bound is loose.
+fn check_p(name: &str, values: &[f64], linf: f64, p: f64, norm: f64) {
+    if linf == 0.0 || !linf.is_finite() {
+        same(name, norm, linf);
+        return;
+    }
+
+    let sum: f64 = values.iter().map(|&x| (x.abs() / linf).powf(p)).sum();
+    let expected = sum.powf(1.0 / p) * linf;
+    let bound = 1e-9 * expected + f64::from_bits(1);
+    assert!(
+        (norm - expected).abs() <= bound || (expected.is_infinite() && norm.is_infinite()),
+        "{name} {p}: {norm:e} differs from {expected:e} by more than {bound:e}"
+    );
+}
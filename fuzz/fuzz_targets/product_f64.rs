@@ -0,0 +1,94 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Entry, Operation, Summer, kernels, product_exp};
+use sums_fuzz::{f64s, gamma, same};
+
+const U: f64 = f64::EPSILON / 2.0;
+
+fuzz_target!(|data: &[u8]| {
+    let values = f64s(data);
+    let expected = naive_product_exp(&values);
+    for kernel in kernels::<f64, (f64, i64)>() {
+        check(kernel.name(), values.len(), kernel.sum(&values), expected);
+    }
+
+    // The product is `product_exp` scaled back, rounded once.
+    let (m, e) = product_exp(&values);
+    let e = e.clamp(-1100, 1100) as i32;
+    let scaled = m * 2f64.powi(e / 2) * 2f64.powi(e - e / 2);
+    for kernel in kernels::<f64, f64>().filter(|kernel| kernel.operation() == Operation::Product) {
+        same(kernel.name(), kernel.sum(&values), scaled);
+    }
+});
+
+// `(m, e)` with 1 <= |m| < 2, or `(product, 0)` for a zero, infinite or NaN
+// product, from one renormalised multiply per value.
+fn naive_product_exp(values: &[f64]) -> (f64, i64) {
+    let negative = values.iter().filter(|x| x.is_sign_negative()).count() % 2 == 1;
+    let sign = if negative { -1.0 } else { 1.0 };
+    let zero = values.contains(&0.0);
+    let infinite = values.iter().any(|x| x.is_infinite());
+    if values.iter().any(|x| x.is_nan()) || (zero && infinite) {
+        return (f64::NAN, 0);
+    }
+    if zero {
+        return (sign * 0.0, 0);
+    }
+    if infinite {
+        return (sign * f64::INFINITY, 0);
+    }
+
+    let (mut mantissa, mut exponent) = (1.0, 0);
+    for &x in values {
+        let (m, e) = split(x.abs());
+        let (m, me) = split(mantissa * m);
+        mantissa = m;
+        exponent += e + me;
+    }
+    (sign * mantissa, exponent)
+}
+
+// `x = m * 2^e` with 1 <= m < 2, for finite positive `x`.
+fn split(x: f64) -> (f64, i64) {
+    let e = x.log2().floor() as i32;
+    // In two halves, as 2^-e alone may not be a normal f64.
+    let mut m = x * 2f64.powi(-e / 2) * 2f64.powi(-e - -e / 2);
+    let mut e = e as i64;
+    // log2 may round to the next integer near a power of two.
+    if m < 1.0 {
+        m *= 2.0;
+        e -= 1;
+    } else if m >= 2.0 {
+        m /= 2.0;
+        e += 1;
+    }
+    (m, e)
+}
+
+// Both products round once per value, so each is within gamma(n) of the
+// exact one; the lanes and their renormalisation add a few dozen roundings.
+fn check(name: &str, len: usize, (m, e): (f64, i64), (expected_m, expected_e): (f64, i64)) {
+    if !expected_m.is_finite() || expected_m == 0.0 {
+        same(name, m, expected_m);
+        assert_eq!(e, 0, "{name}: exponent {e} of a special product");
+        return;
+    }
+
+    assert!(
+        (1.0..2.0).contains(&m.abs()),
+        "{name}: mantissa {m} out of [1, 2)"
+    );
+    let shift = e - expected_e;
+    assert!(
+        shift.abs() <= 1,
+        "{name}: {m} * 2^{e} instead of about {expected_m} * 2^{expected_e}"
+    );
+    let m = m * 2f64.powi(shift as i32);
+    let bound = (gamma(len + 48, U) + gamma(len, U)) * expected_m.abs();
+    assert!(
+        (m - expected_m).abs() <= bound,
+        "{name}: {m} * 2^{expected_e} differs from {expected_m} * 2^{expected_e} by more than \
+         {bound:e}"
+    );
+}
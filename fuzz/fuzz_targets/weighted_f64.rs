@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sums::{Entry, Summer, Weighted, kernels};
+use sums_fuzz::{F64_LIMIT, Reference, f64s};
+
+const U: f64 = f64::EPSILON / 2.0;
+
+// The first half of the values are the values, the second half the weights.
+fuzz_target!(|data: &[u8]| {
+    let values = f64s(data);
+    let (values, weights) = values.split_at(values.len() / 2);
+    let weights = &weights[..values.len()];
+
+    let reference = Reference::weighted(values, weights);
+    for kernel in kernels::<Weighted, f64>() {
+        let sum = kernel.sum((values, weights));
+        reference.check(kernel.name(), kernel.accuracy(), U, F64_LIMIT, sum);
+    }
+});
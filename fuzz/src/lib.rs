@@ -0,0 +1,411 @@
+use sums::{
+    Accuracy, FoldSum, SumExt, chunked_sum, expanded_fold_sum, expanded_fold_sum_with_scratch,
+    fold_sum, fold_sum_scratch_len, fold_sum_with_scratch, prefetch_fold_sum,
+};
+
+// Bounds on sum |x| below which no kernel can overflow an intermediate sum,
+// so results are checked against the exact sum; above, only for an infinity or
+// NaN that the overflow can explain.
+pub const F64_LIMIT: f64 = 1e300;
+pub const F32_LIMIT: f32 = 1e30;
+
+// The input bytes as little-endian values; trailing bytes are dropped.
+pub fn f64s(data: &[u8]) -> Vec<f64> {
+    data.as_chunks::<8>()
+        .0
+        .iter()
+        .map(|&bytes| f64::from_le_bytes(bytes))
+        .collect()
+}
+
+pub fn f32s(data: &[u8]) -> Vec<f32> {
+    data.as_chunks::<4>()
+        .0
+        .iter()
+        .map(|&bytes| f32::from_le_bytes(bytes))
+        .collect()
+}
+
+pub fn u16s(data: &[u8]) -> Vec<u16> {
+    data.as_chunks::<2>()
+        .0
+        .iter()
+        .map(|&bytes| u16::from_le_bytes(bytes))
+        .collect()
+}
+
+// What every kernel must return for one input, from exact accumulation of the
+// values widened to f64.
+pub struct Reference {
+    len: usize,
+    // The exact sum and sum |x| of the finite values, and the sums of their
+    // positive and negative magnitudes, each rounded once.
+    exact: f64,
+    abs: f64,
+    positive: f64,
+    negative: f64,
+    nan: bool,
+    pos_inf: bool,
+    neg_inf: bool,
+    // Absolute error allowed on top of the bound, for terms that were
+    // rounded before they were added.
+    slack: f64,
+}
+
+impl Reference {
+    pub fn new(values: impl IntoIterator<Item = f64>) -> Self {
+        Self::with_counts(values.into_iter().map(|x| (x, 1)))
+    }
+
+    // The reference for `values` repeated cyclically to `len` values, without
+    // adding every repetition.
+    pub fn tiled(values: &[f64], len: usize) -> Self {
+        let (full, extra) = (len / values.len(), len % values.len());
+        let counts = (0..values.len()).map(|i| full + usize::from(i < extra));
+        Self::with_counts(values.iter().copied().zip(counts).filter(|&(_, n)| n > 0))
+    }
+
+    // The reference for sum values[i] * weights[i]: each product that does not
+    // overflow is added exactly as its rounded value and its FMA error. Below
+    // the normal range the error is not exact, which `slack` allows for. A
+    // product of finite values that overflows only makes an infinity of its
+    // sign possible, since a fused kernel may still bring the sum back.
+    pub fn weighted(values: &[f64], weights: &[f64]) -> Self {
+        let (mut pos_overflow, mut neg_overflow) = (false, false);
+        let mut terms = Vec::new();
+        for (&x, &w) in values.iter().zip(weights) {
+            let p = x * w;
+            if p.is_infinite() && x.is_finite() && w.is_finite() {
+                if p > 0.0 {
+                    pos_overflow = true;
+                } else {
+                    neg_overflow = true;
+                }
+            } else if p.is_finite() {
+                terms.push(p);
+                terms.push(x.mul_add(w, -p));
+            } else {
+                terms.push(p);
+            }
+        }
+
+        let mut reference = Self::new(terms);
+        if pos_overflow {
+            reference.positive = f64::INFINITY;
+            reference.abs = f64::INFINITY;
+        }
+        if neg_overflow {
+            reference.negative = f64::INFINITY;
+            reference.abs = f64::INFINITY;
+        }
+        reference.slack = values.len() as f64 * f64::from_bits(1);
+        reference
+    }
+
+    // The exact sum of the finite values, rounded once.
+    pub fn exact(&self) -> f64 {
+        self.exact
+    }
+
+    fn with_counts(values: impl Iterator<Item = (f64, usize)>) -> Self {
+        let mut reference = Self {
+            len: 0,
+            exact: 0.0,
+            abs: 0.0,
+            positive: 0.0,
+            negative: 0.0,
+            nan: false,
+            pos_inf: false,
+            neg_inf: false,
+            slack: 0.0,
+        };
+        let (mut exact, mut abs) = (Exact::default(), Exact::default());
+        for (x, count) in values {
+            reference.len += count;
+            if x.is_nan() {
+                reference.nan = true;
+            } else if x == f64::INFINITY {
+                reference.pos_inf = true;
+            } else if x == f64::NEG_INFINITY {
+                reference.neg_inf = true;
+            } else {
+                exact.add(x, count as u64);
+                abs.add(x.abs(), count as u64);
+            }
+        }
+        reference.exact = exact.sum();
+        reference.abs = abs.sum();
+        reference.positive = exact.positive.round();
+        reference.negative = exact.negative.round();
+        reference
+    }
+
+    // Panics unless `result` is what a kernel of class `accuracy` may return
+    // with unit roundoff `u` (half an ulp of 1 in the accumulator):
+    // - NaN for a NaN or both infinities, else the infinity in the input;
+    // - an infinity only from that infinity or from finite values of that sign
+    //   whose sum reaches `limit`, and NaN only when both signs do;
+    // - for finite input whose sum |x| stays below `limit`, the exact sum
+    //   within the error bound of the class.
+    pub fn check(&self, name: &str, accuracy: Accuracy, u: f64, limit: f64, result: f64) {
+        if self.nan || (self.pos_inf && self.neg_inf) {
+            assert!(result.is_nan(), "{name}: {result} instead of NaN");
+            return;
+        }
+
+        let pos_overflow = self.pos_inf || self.positive >= limit;
+        let neg_overflow = self.neg_inf || self.negative >= limit;
+        if result.is_nan() {
+            assert!(
+                pos_overflow && neg_overflow,
+                "{name}: NaN for input without NaN whose positive and negative sums {:e} and \
+                 -{:e} cannot both overflow",
+                self.positive,
+                self.negative
+            );
+            return;
+        }
+        if result.is_infinite() {
+            let (overflow, other_inf) = if result > 0.0 {
+                (pos_overflow, self.neg_inf)
+            } else {
+                (neg_overflow, self.pos_inf)
+            };
+            assert!(
+                overflow && !other_inf,
+                "{name}: {result} for input whose positive and negative sums are {:e} and -{:e} \
+                 (infinities: +{}, -{})",
+                self.positive,
+                self.negative,
+                self.pos_inf,
+                self.neg_inf
+            );
+            return;
+        }
+        assert!(
+            !self.pos_inf && !self.neg_inf,
+            "{name}: {result} for input with an infinity"
+        );
+
+        if self.abs >= limit {
+            return;
+        }
+        let error = (result - self.exact).abs();
+        let bound = self.bound(accuracy, u);
+        assert!(
+            error <= bound,
+            "{name}: {result:e} differs from the exact {:e} by {error:e}, more than {bound:e} \
+             ({accuracy:?}, {} values, sum |x| = {:e})",
+            self.exact,
+            self.len,
+            self.abs
+        );
+    }
+
+    // Every kernel adds in a tree, whose error is at most gamma(depth) * sum
+    // |x| with gamma(k) = k u / (1 - k u). The lane kernels keep at least 8
+    // lanes per sum (16 lanes, or 8 per part for complex values) and fold
+    // them and the remainder in a few dozen more adds; the blocked ones are
+    // never deeper. The exact sum is itself rounded, so u |exact| is added.
+    fn bound(&self, accuracy: Accuracy, u: f64) -> f64 {
+        let gamma = |depth: usize| gamma(depth, u);
+        let rounding = u * self.exact.abs();
+        let bound = match accuracy {
+            Accuracy::Recursive => gamma(self.len) * self.abs + rounding,
+            Accuracy::Lanes | Accuracy::Blocked => {
+                gamma(self.len.min(self.len.div_ceil(8) + 32)) * self.abs + rounding
+            }
+            // The lo parts are recursive sums of the hi errors; the result
+            // is rounded once more from the double-double.
+            Accuracy::Compensated => 2.0 * gamma(self.len + 16).powi(2) * self.abs + 2.0 * rounding,
+            Accuracy::CorrectlyRounded | Accuracy::Exact => 0.0,
+        };
+        // Room for the rounding of `error` and of the bound itself.
+        bound * (1.0 + 8.0 * u) + self.slack
+    }
+}
+
+// gamma(k) = k u / (1 - k u) bounds the relative error of k roundings.
+pub fn gamma(depth: usize, u: f64) -> f64 {
+    let ku = depth as f64 * u;
+    ku / (1.0 - ku)
+}
+
+// The streaming forms, `prefetch_fold_sum` and the scratch variants promise
+// the same bits as the slice kernels; the stream takes `split` values as a
+// slice, then one by one.
+pub fn same_as_slice_kernels(values: &[f64], split: usize) {
+    same(
+        "fast_sum",
+        values.iter().copied().fast_sum(),
+        chunked_sum(values),
+    );
+    same(
+        "SumExt::fold_sum",
+        values.iter().copied().fold_sum(),
+        fold_sum(values),
+    );
+    let mut stream = FoldSum::new();
+    let (head, tail) = values.split_at(split.min(values.len()));
+    stream.add_slice(head);
+    for &x in tail {
+        stream.add(x);
+    }
+    same("FoldSum", stream.sum(), fold_sum(values));
+    same(
+        "prefetch_fold_sum",
+        prefetch_fold_sum(values),
+        fold_sum(values),
+    );
+    let mut scratch = vec![0.0; fold_sum_scratch_len(values.len())];
+    same(
+        "fold_sum_with_scratch",
+        fold_sum_with_scratch(values, &mut scratch),
+        fold_sum(values),
+    );
+    same(
+        "expanded_fold_sum_with_scratch",
+        expanded_fold_sum_with_scratch(values, &mut scratch),
+        expanded_fold_sum(values),
+    );
+}
+
+pub fn same(name: &str, a: f64, b: f64) {
+    assert!(
+        a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan()),
+        "{name}: {a:e} and {b:e} differ"
+    );
+}
+
+// Limbs of 64 bits for fixed-point sums in units of 2^-1074: 2098 bits cover
+// every finite f64, and the rest leaves room for 2^100 values.
+const LIMBS: usize = 35;
+
+// An exact sum of finite values, independent of the crate's `ReproducibleSum`
+// so that `reproducible_sum` is not checked against itself: the positive and
+// negative values are added into separate magnitudes, which are subtracted and
+// rounded to nearest even only at the end.
+#[derive(Clone, Default)]
+struct Exact {
+    positive: Magnitude,
+    negative: Magnitude,
+}
+
+impl Exact {
+    // Adds `count` times x.
+    fn add(&mut self, x: f64, count: u64) {
+        let bits = x.to_bits();
+        let field = (bits >> 52) & 0x7ff;
+        let fraction = bits & ((1 << 52) - 1);
+        // x = significand * 2^(shift - 1074)
+        let (significand, shift) = if field == 0 {
+            (fraction, 0)
+        } else {
+            (fraction | (1 << 52), field - 1)
+        };
+        let magnitude = if x.is_sign_negative() {
+            &mut self.negative
+        } else {
+            &mut self.positive
+        };
+        let product = significand as u128 * count as u128;
+        magnitude.add(product as u64, shift as usize);
+        magnitude.add((product >> 64) as u64, shift as usize + 64);
+    }
+
+    fn sum(&self) -> f64 {
+        if self.positive >= self.negative {
+            self.positive.minus(&self.negative).round()
+        } else {
+            -self.negative.minus(&self.positive).round()
+        }
+    }
+}
+
+// Little-endian limbs; compared as numbers through the reversed limbs.
+#[derive(Clone, PartialEq, Eq)]
+struct Magnitude([u64; LIMBS]);
+
+impl Default for Magnitude {
+    fn default() -> Self {
+        Self([0; LIMBS])
+    }
+}
+
+impl PartialOrd for Magnitude {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Magnitude {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl Magnitude {
+    fn add(&mut self, significand: u64, shift: usize) {
+        let mut i = shift / 64;
+        let mut addend = (significand as u128) << (shift % 64);
+        while addend != 0 {
+            let (sum, overflow) = self.0[i].overflowing_add(addend as u64);
+            self.0[i] = sum;
+            addend = (addend >> 64) + overflow as u128;
+            i += 1;
+        }
+    }
+
+    // self - other, for self >= other.
+    fn minus(&self, other: &Self) -> Self {
+        let mut difference = Self::default();
+        let mut borrow = false;
+        for i in 0..LIMBS {
+            let (d, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            difference.0[i] = d;
+            borrow = b1 || b2;
+        }
+        difference
+    }
+
+    // Bits start..start + count, for count <= 64.
+    fn bits(&self, start: usize, count: usize) -> u64 {
+        let (limb, offset) = (start / 64, start % 64);
+        let mut bits = self.0[limb] >> offset;
+        if offset > 0 && limb + 1 < LIMBS {
+            bits |= self.0[limb + 1] << (64 - offset);
+        }
+        if count < 64 {
+            bits & ((1 << count) - 1)
+        } else {
+            bits
+        }
+    }
+
+    // Rounds to the nearest f64, ties to even. A magnitude m * 2^shift with
+    // 2^52 <= m <= 2^53 has the bit pattern (shift << 52) + m, also when
+    // rounding carries into the next binade or below 2^53 into the subnormals.
+    fn round(&self) -> f64 {
+        let Some(top_limb) = self.0.iter().rposition(|&limb| limb != 0) else {
+            return 0.0;
+        };
+        let top = top_limb * 64 + 63 - self.0[top_limb].leading_zeros() as usize;
+        if top < 53 {
+            return f64::from_bits(self.0[0]);
+        }
+
+        let shift = top - 52;
+        let infinity = f64::INFINITY.to_bits();
+        if shift as u64 >= infinity >> 52 {
+            return f64::INFINITY;
+        }
+        let mut mantissa = self.bits(shift, 53);
+        let half = self.bits(shift - 1, 1) == 1;
+        let sticky = (0..shift - 1).any(|i| self.bits(i, 1) == 1);
+        if half && (sticky || mantissa & 1 == 1) {
+            mantissa += 1;
+        }
+        f64::from_bits((((shift as u64) << 52) + mantissa).min(infinity))
+    }
+}
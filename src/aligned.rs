@@ -0,0 +1,155 @@
+use alloc::alloc::{Layout, alloc, dealloc, handle_alloc_error};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+// Where an `AlignedBuffer` starts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    // 64 bytes: no vector load of the kernels crosses a cache line.
+    CacheLine,
+    // 4 KiB: also the start of a page.
+    Page,
+    // 2 MiB, with the whole allocation advised as `MADV_HUGEPAGE` on Linux,
+    // so transparent huge pages back it even in `madvise` mode and a stream
+    // over it misses the TLB once per 2 MiB instead of once per 4 KiB.
+    HugePage,
+}
+
+impl Alignment {
+    pub fn bytes(self) -> usize {
+        match self {
+            Alignment::CacheLine => 64,
+            Alignment::Page => 4 << 10,
+            Alignment::HugePage => 2 << 20,
+        }
+    }
+}
+
+// A fixed-length, zero-initialised `[f64]` at a chosen alignment, so that the
+// alignment and page size seen by a kernel do not depend on the allocator.
+// Misaligned inputs for comparison are subslices, e.g. `&buffer[1..]`.
+pub struct AlignedBuffer {
+    ptr: NonNull<f64>,
+    len: usize,
+    alignment: Alignment,
+}
+
+// SAFETY: the buffer owns its values like a `Vec<f64>`.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    pub fn new(len: usize, alignment: Alignment) -> Self {
+        let layout = Self::layout(len, alignment);
+        if layout.size() == 0 {
+            let ptr = core::ptr::without_provenance_mut(alignment.bytes());
+            return Self {
+                ptr: NonNull::new(ptr).unwrap(),
+                len,
+                alignment,
+            };
+        }
+
+        // SAFETY: the layout has a non-zero size.
+        let ptr = unsafe { alloc(layout) }.cast::<f64>();
+        let Some(ptr) = NonNull::new(ptr) else {
+            handle_alloc_error(layout);
+        };
+        // Advised before the first write, so that the pages are faulted in as
+        // huge pages rather than collapsed later, if at all.
+        if alignment == Alignment::HugePage {
+            advise_huge_pages(ptr.as_ptr().cast(), layout.size());
+        }
+        // SAFETY: `ptr` is valid for `len` values.
+        unsafe { ptr.as_ptr().write_bytes(0, len) };
+        Self {
+            ptr,
+            len,
+            alignment,
+        }
+    }
+
+    pub fn from_slice(values: &[f64], alignment: Alignment) -> Self {
+        let mut buffer = Self::new(values.len(), alignment);
+        buffer.copy_from_slice(values);
+        buffer
+    }
+
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    // Huge pages are advised in whole pages, so that allocation is rounded up
+    // to a multiple of 2 MiB.
+    fn layout(len: usize, alignment: Alignment) -> Layout {
+        let size = len
+            .checked_mul(size_of::<f64>())
+            .and_then(|size| match alignment {
+                Alignment::HugePage => size.checked_next_multiple_of(alignment.bytes()),
+                _ => Some(size),
+            })
+            .expect("AlignedBuffer too large");
+        Layout::from_size_align(size, alignment.bytes()).expect("AlignedBuffer too large")
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        let layout = Self::layout(self.len, self.alignment);
+        if layout.size() != 0 {
+            // SAFETY: allocated in `new` with the same layout.
+            unsafe { dealloc(self.ptr.as_ptr().cast(), layout) };
+        }
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        // SAFETY: `ptr` holds `len` initialised values, or is a dangling
+        // aligned pointer for an empty buffer.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [f64] {
+        // SAFETY: as in `deref`, and `&mut self` is unique.
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Clone for AlignedBuffer {
+    fn clone(&self) -> Self {
+        Self::from_slice(self, self.alignment)
+    }
+}
+
+impl core::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("len", &self.len)
+            .field("alignment", &self.alignment)
+            .finish_non_exhaustive()
+    }
+}
+
+// The advice is only a hint: without transparent huge pages it fails, and the
+// buffer stays on normal pages.
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn advise_huge_pages(address: *mut core::ffi::c_void, len: usize) {
+    const MADV_HUGEPAGE: core::ffi::c_int = 14;
+    unsafe extern "C" {
+        fn madvise(
+            address: *mut core::ffi::c_void,
+            len: usize,
+            advice: core::ffi::c_int,
+        ) -> core::ffi::c_int;
+    }
+    // SAFETY: the range is one page-aligned allocation of `len` bytes.
+    unsafe { madvise(address, len, MADV_HUGEPAGE) };
+}
+
+#[cfg(not(all(feature = "std", target_os = "linux")))]
+fn advise_huge_pages(_address: *mut core::ffi::c_void, _len: usize) {}
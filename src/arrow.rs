@@ -0,0 +1,102 @@
+use arrow_array::types::{
+    Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type,
+    UInt32Type, UInt64Type,
+};
+use arrow_array::{Array, ArrowPrimitiveType, PrimitiveArray};
+use arrow_schema::{ArrowError, DataType};
+use std::format;
+
+use crate::{BLOCK, FoldSum};
+
+// Sums a float or integer Arrow array, skipping null slots. Values are widened
+// to f64; the sum equals `fold_sum` over the non-null values.
+pub fn sum_arrow_array(array: &dyn Array) -> Result<f64, ArrowError> {
+    sum_arrow_arrays([array])
+}
+
+// Sums the chunks of a chunked column as one column.
+pub fn sum_arrow_arrays<'a>(
+    arrays: impl IntoIterator<Item = &'a dyn Array>,
+) -> Result<f64, ArrowError> {
+    let mut sum = FoldSum::new();
+    for array in arrays {
+        add_array(&mut sum, array)?;
+    }
+    Ok(sum.sum())
+}
+
+fn add_array(sum: &mut FoldSum, array: &dyn Array) -> Result<(), ArrowError> {
+    match array.data_type() {
+        DataType::Float64 => add_primitive::<Float64Type>(sum, array, |x| x),
+        DataType::Float32 => add_primitive::<Float32Type>(sum, array, |x| x as f64),
+        DataType::Int8 => add_primitive::<Int8Type>(sum, array, |x| x as f64),
+        DataType::Int16 => add_primitive::<Int16Type>(sum, array, |x| x as f64),
+        DataType::Int32 => add_primitive::<Int32Type>(sum, array, |x| x as f64),
+        DataType::Int64 => add_primitive::<Int64Type>(sum, array, |x| x as f64),
+        DataType::UInt8 => add_primitive::<UInt8Type>(sum, array, |x| x as f64),
+        DataType::UInt16 => add_primitive::<UInt16Type>(sum, array, |x| x as f64),
+        DataType::UInt32 => add_primitive::<UInt32Type>(sum, array, |x| x as f64),
+        DataType::UInt64 => add_primitive::<UInt64Type>(sum, array, |x| x as f64),
+        DataType::Null => {}
+        data_type => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "cannot sum a column of type {data_type}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+// Null slots hold arbitrary values, so only the runs of valid slots are summed.
+fn add_primitive<T: ArrowPrimitiveType>(
+    sum: &mut FoldSum,
+    array: &dyn Array,
+    widen: impl Fn(T::Native) -> f64 + Copy,
+) {
+    let array = array
+        .as_any()
+        .downcast_ref::<PrimitiveArray<T>>()
+        .expect("data type matches array type");
+    let values = array.values();
+
+    match array.nulls().filter(|nulls| nulls.null_count() > 0) {
+        None => add_widened(sum, values, widen),
+        Some(nulls) => {
+            for (start, end) in nulls.valid_slices() {
+                add_widened(sum, &values[start..end], widen);
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn add_widened<N: Copy>(sum: &mut FoldSum, values: &[N], widen: impl Fn(N) -> f64) {
+    let mut block = [0.0; BLOCK];
+    for chunk in values.chunks(BLOCK) {
+        for (out, &x) in block.iter_mut().zip(chunk) {
+            *out = widen(x);
+        }
+        sum.add_slice(&block[..chunk.len()]);
+    }
+}
+
+// Sums the named top-level column of a local Parquet file, reading one record
+// batch at a time.
+#[cfg(feature = "parquet")]
+pub fn sum_parquet_column(
+    path: impl AsRef<std::path::Path>,
+    column: &str,
+) -> Result<f64, parquet::errors::ParquetError> {
+    use parquet::arrow::ProjectionMask;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(path)?)?;
+    builder.schema().index_of(column)?;
+    let mask = ProjectionMask::columns(builder.parquet_schema(), [column]);
+
+    let mut sum = FoldSum::new();
+    for batch in builder.with_projection(mask).build()? {
+        add_array(&mut sum, batch?.column(0))?;
+    }
+    Ok(sum.sum())
+}
@@ -0,0 +1,384 @@
+// Reading the assembly for `sums asm`; part of the binary, not the library.
+
+// A function in the assembly emitted by `--emit asm` (AT&T syntax), with its
+// symbol demangled and the hash dropped, e.g. `sums::fold_sum`.
+#[derive(Clone, Debug)]
+pub struct AsmFunction<'a> {
+    pub name: String,
+    pub lines: Vec<&'a str>,
+}
+
+// The adds in the hottest loop of a function: the loop, found by a backward
+// jump, whose floating-point adds cover the most elements per iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoopReport {
+    // Widest register of a packed add (128, 256 or 512), 0 if there are none.
+    pub vector_bits: u32,
+    pub vector_adds: usize,
+    pub scalar_adds: usize,
+    // Registers read by an add before the loop body writes them, i.e. the
+    // independent dependency chains carried from one iteration to the next.
+    pub loop_carried: usize,
+}
+
+impl LoopReport {
+    pub fn is_vectorised(&self) -> bool {
+        self.vector_bits > 0
+    }
+}
+
+pub fn asm_functions(asm: &str) -> Vec<AsmFunction<'_>> {
+    let mut functions = Vec::new();
+    let mut current: Option<AsmFunction> = None;
+
+    for line in asm.lines() {
+        if let Some(symbol) = line.strip_suffix(':')
+            && let Some(name) = demangle(symbol)
+        {
+            functions.extend(current.take());
+            current = Some(AsmFunction {
+                name,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        if let Some(function) = &mut current {
+            if line.starts_with(".Lfunc_end") || line.trim() == ".cfi_endproc" {
+                functions.extend(current.take());
+            } else {
+                function.lines.push(line);
+            }
+        }
+    }
+    functions.extend(current);
+    functions
+}
+
+// Legacy (`_ZN`) and v0 (`_R`, `-C symbol-mangling-version=v0`) symbols; see
+// `demangle_legacy` and `demangle_v0` for what each handles. Anything else,
+// such as a local label, gives `None`.
+pub fn demangle(symbol: &str) -> Option<String> {
+    if let Some(rest) = symbol.strip_prefix("_ZN") {
+        demangle_legacy(rest)
+    } else if let Some(rest) = symbol.strip_prefix("_R") {
+        V0 { rest }.symbol()
+    } else {
+        None
+    }
+}
+
+// `_ZN4sums8fold_sum17h0123456789abcdefE` -> `sums::fold_sum`.
+fn demangle_legacy(mut rest: &str) -> Option<String> {
+    let mut name = String::new();
+
+    while !rest.starts_with('E') {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let len: usize = rest[..digits].parse().ok()?;
+        let ident = rest.get(digits..digits + len)?;
+        rest = &rest[digits + len..];
+
+        let is_hash = ident.len() == 17
+            && ident.starts_with('h')
+            && ident[1..].bytes().all(|b| b.is_ascii_hexdigit());
+        if is_hash && rest == "E" {
+            break;
+        }
+        if !name.is_empty() {
+            name.push_str("::");
+        }
+        unescape(ident, &mut name);
+    }
+    Some(name)
+}
+
+// `_RNvCs1a2b3c_4sums8fold_sum` -> `sums::fold_sum`, and closures as
+// `sums::f::{closure#0}`. Only crate roots and nested paths are handled, which
+// covers every non-generic function; generic arguments, impl paths, back
+// references and punycode identifiers give `None`. Disambiguators, like the
+// legacy hash, are dropped, and so is the instantiating crate that may follow.
+struct V0<'a> {
+    rest: &'a str,
+}
+
+impl V0<'_> {
+    fn symbol(mut self) -> Option<String> {
+        // An optional encoding version.
+        self.rest = self.rest.trim_start_matches(|c: char| c.is_ascii_digit());
+        self.path()
+    }
+
+    fn path(&mut self) -> Option<String> {
+        match self.next()? {
+            'C' => Some(self.identifier()?.0.into()),
+            'N' => {
+                let namespace = self.next()?;
+                let parent = self.path()?;
+                let (name, disambiguator) = self.identifier()?;
+                match namespace {
+                    'C' => Some(format!("{parent}::{{closure#{disambiguator}}}")),
+                    'a'..='z' => Some(format!("{parent}::{name}")),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // An identifier and its disambiguator: `[s<base-62>] <len> [_] <bytes>`,
+    // where the `_` separates bytes that start with a digit or `_`.
+    fn identifier(&mut self) -> Option<(&str, u64)> {
+        let disambiguator = if self.rest.starts_with('s') {
+            self.next();
+            self.base_62()? + 1
+        } else {
+            0
+        };
+        let digits = self.rest.bytes().take_while(u8::is_ascii_digit).count();
+        let len: usize = self.rest[..digits].parse().ok()?;
+        let rest = &self.rest[digits..];
+        let rest = rest.strip_prefix('_').unwrap_or(rest);
+        let name = rest.get(..len)?;
+        self.rest = &rest[len..];
+        Some((name, disambiguator))
+    }
+
+    // `_` is 0, otherwise the digits 0-9a-zA-Z plus one, up to `_`.
+    fn base_62(&mut self) -> Option<u64> {
+        let (digits, rest) = self.rest.split_once('_')?;
+        self.rest = rest;
+        if digits.is_empty() {
+            return Some(0);
+        }
+        let mut value: u64 = 0;
+        for c in digits.chars() {
+            let digit = match c {
+                '0'..='9' => c as u64 - '0' as u64,
+                'a'..='z' => c as u64 - 'a' as u64 + 10,
+                'A'..='Z' => c as u64 - 'A' as u64 + 36,
+                _ => return None,
+            };
+            value = value.checked_mul(62)?.checked_add(digit)?;
+        }
+        value.checked_add(1)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.rest.chars().next()?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Some(c)
+    }
+}
+
+fn unescape(mut ident: &str, out: &mut String) {
+    // Identifiers starting with an escape get a leading underscore.
+    if ident.starts_with("_$") {
+        ident = &ident[1..];
+    }
+    while !ident.is_empty() {
+        if let Some(rest) = ident.strip_prefix("..") {
+            out.push_str("::");
+            ident = rest;
+        } else if let Some(rest) = ident.strip_prefix('$')
+            && let Some((escape, rest)) = rest.split_once('$')
+        {
+            match escape {
+                "LT" => out.push('<'),
+                "GT" => out.push('>'),
+                "C" => out.push(','),
+                "RF" => out.push('&'),
+                "BP" => out.push('*'),
+                "SP" => out.push('@'),
+                "LP" => out.push('('),
+                "RP" => out.push(')'),
+                _ => match escape
+                    .strip_prefix('u')
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32)
+                {
+                    Some(c) => out.push(c),
+                    None => {
+                        out.push('$');
+                        out.push_str(escape);
+                        out.push('$');
+                    }
+                },
+            }
+            ident = rest;
+        } else {
+            let c = ident.chars().next().unwrap_or_default();
+            out.push(c);
+            ident = &ident[c.len_utf8()..];
+        }
+    }
+}
+
+impl AsmFunction<'_> {
+    // The functions this one calls or tail-calls, demangled like `name`.
+    pub fn callees(&self) -> impl Iterator<Item = String> + '_ {
+        self.lines.iter().filter_map(|line| {
+            let (mnemonic, operand) = instruction(line)?;
+            if mnemonic != "call" && mnemonic != "callq" && !mnemonic.starts_with('j') {
+                return None;
+            }
+            let symbol = operand.trim().trim_end_matches("@PLT");
+            demangle(symbol)
+        })
+    }
+
+    pub fn hot_loop(&self) -> Option<LoopReport> {
+        let label_line = |label: &str| {
+            self.lines
+                .iter()
+                .position(|line| line.strip_suffix(':') == Some(label))
+        };
+
+        let mut best: Option<(usize, usize, u32)> = None;
+        for (end, line) in self.lines.iter().enumerate() {
+            let Some((mnemonic, operands)) = instruction(line) else {
+                continue;
+            };
+            if !mnemonic.starts_with('j') {
+                continue;
+            }
+            let Some(start) = label_line(operands.trim()).filter(|&start| start < end) else {
+                continue;
+            };
+            let elements: u32 = self.lines[start..=end]
+                .iter()
+                .filter_map(|line| add(line))
+                .map(|add| add.elements())
+                .sum();
+            // Most elements first, then the innermost loop.
+            let better = match best {
+                None => true,
+                Some((best_start, best_end, best_elements)) => {
+                    elements > best_elements
+                        || (elements == best_elements && end - start < best_end - best_start)
+                }
+            };
+            if elements > 0 && better {
+                best = Some((start, end, elements));
+            }
+        }
+
+        let (start, end, _) = best?;
+        Some(loop_report(&self.lines[start..=end]))
+    }
+}
+
+fn loop_report(lines: &[&str]) -> LoopReport {
+    let mut report = LoopReport::default();
+    let adds: Vec<Add> = lines.iter().filter_map(|line| add(line)).collect();
+
+    let mut written = [false; 32];
+    let mut carried = [false; 32];
+    let written_in_loop = |register: usize| adds.iter().any(|add| add.destination == register);
+    for add in &adds {
+        if add.packed {
+            report.vector_adds += 1;
+            report.vector_bits = report.vector_bits.max(add.bits);
+        } else {
+            report.scalar_adds += 1;
+        }
+        for &source in add.sources.iter().flatten() {
+            if !written[source] && written_in_loop(source) {
+                carried[source] = true;
+            }
+        }
+        written[add.destination] = true;
+    }
+    report.loop_carried = carried.iter().filter(|&&carried| carried).count();
+    report
+}
+
+struct Add {
+    packed: bool,
+    bits: u32,
+    element_bits: u32,
+    sources: [Option<usize>; 2],
+    destination: usize,
+}
+
+// A floating-point add, `[v]add{p,s}{s,d} src, [src,] dst`.
+fn add(line: &str) -> Option<Add> {
+    let (mnemonic, operands) = instruction(line)?;
+    let kind = mnemonic.strip_prefix('v').unwrap_or(mnemonic);
+    let (packed, element_bits) = match kind {
+        "addpd" => (true, 64),
+        "addps" => (true, 32),
+        "addsd" => (false, 64),
+        "addss" => (false, 32),
+        _ => return None,
+    };
+
+    let operands = split_operands(operands);
+    let (&destination, sources) = operands.split_last()?;
+    let (destination, bits) = vector_register(destination)?;
+    let mut source_registers = [None; 2];
+    for (slot, source) in source_registers.iter_mut().zip(sources) {
+        *slot = vector_register(source).map(|(register, _)| register);
+    }
+    // The SSE forms read their destination.
+    if !mnemonic.starts_with('v') {
+        source_registers[1] = Some(destination);
+    }
+
+    Some(Add {
+        packed,
+        bits,
+        element_bits,
+        sources: source_registers,
+        destination,
+    })
+}
+
+impl Add {
+    fn elements(&self) -> u32 {
+        if self.packed {
+            self.bits / self.element_bits
+        } else {
+            1
+        }
+    }
+}
+
+fn instruction(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('.') || line.ends_with(':') || line.starts_with('#') {
+        return None;
+    }
+    Some(line.split_once(char::is_whitespace).unwrap_or((line, "")))
+}
+
+// Operands are separated by commas outside of memory references.
+fn split_operands(operands: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, byte) in operands.bytes().enumerate() {
+        match byte {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b',' if depth == 0 => {
+                parts.push(operands[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(operands[start..].trim());
+    parts
+}
+
+// `%ymm3` -> (3, 256); masks such as `%zmm0 {%k1}` are ignored.
+fn vector_register(operand: &str) -> Option<(usize, u32)> {
+    let register = operand.split_whitespace().next()?.strip_prefix('%')?;
+    let bits = match register.get(..3)? {
+        "xmm" => 128,
+        "ymm" => 256,
+        "zmm" => 512,
+        _ => return None,
+    };
+    let index = register[3..].parse().ok().filter(|&index| index < 32)?;
+    Some((index, bits))
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+// Data cache sizes in bytes, as seen by one core.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheSizes {
+    pub l1: usize,
+    pub l2: usize,
+    pub l3: usize,
+}
+
+impl Default for CacheSizes {
+    fn default() -> Self {
+        Self {
+            l1: 32 << 10,
+            l2: 1 << 20,
+            l3: 32 << 20,
+        }
+    }
+}
+
+impl CacheSizes {
+    // Reads the cache hierarchy of cpu0 from sysfs on Linux; levels that cannot
+    // be read, and every level on other systems, keep the `Default` sizes.
+    pub fn detect() -> Self {
+        let mut sizes = Self::default();
+        let Ok(entries) = fs::read_dir("/sys/devices/system/cpu/cpu0/cache") else {
+            return sizes;
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let dir = entry.path();
+            let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+            if !entry.file_name().to_string_lossy().starts_with("index")
+                || read("type").is_some_and(|kind| kind.trim() == "Instruction")
+            {
+                continue;
+            }
+            let (Some(level), Some(size)) = (read("level"), cache_size(&dir)) else {
+                continue;
+            };
+            match level.trim() {
+                "1" => sizes.l1 = size,
+                "2" => sizes.l2 = size,
+                "3" => sizes.l3 = size,
+                _ => {}
+            }
+        }
+        sizes
+    }
+}
+
+// `size` is written as e.g. "48K", "2048K" or "32M".
+fn cache_size(dir: &Path) -> Option<usize> {
+    let size = fs::read_to_string(dir.join("size")).ok()?;
+    let size = size.trim();
+    let (digits, shift) = match size.as_bytes().last()? {
+        b'K' => (&size[..size.len() - 1], 10),
+        b'M' => (&size[..size.len() - 1], 20),
+        b'G' => (&size[..size.len() - 1], 30),
+        _ => (size, 0),
+    };
+    digits.parse::<usize>().ok().map(|size| size << shift)
+}
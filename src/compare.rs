@@ -0,0 +1,285 @@
+use std::fmt::Write as _;
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::{format, vec};
+
+// Per-call times of one kernel at one input size, as recorded by `sums record`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchSamples {
+    pub name: String,
+    pub n: usize,
+    pub nanos: Vec<f64>,
+}
+
+// `{"runs": [{"name": "fold_sum", "n": 131072, "nanos": [1.0, ...]}, ...]}`
+pub fn samples_to_json(runs: &[BenchSamples]) -> String {
+    let mut json = String::from("{\n  \"runs\": [\n");
+    for (i, run) in runs.iter().enumerate() {
+        let nanos: Vec<String> = run.nanos.iter().map(|x| format!("{x:?}")).collect();
+        let _ = write!(
+            json,
+            "    {{\"name\": \"{}\", \"n\": {}, \"nanos\": [{}]}}",
+            escape(&run.name),
+            run.n,
+            nanos.join(", ")
+        );
+        json.push_str(if i + 1 < runs.len() { ",\n" } else { "\n" });
+    }
+    json.push_str("  ]\n}\n");
+    json
+}
+
+pub fn parse_samples(json: &str) -> Result<Vec<BenchSamples>, String> {
+    let mut parser = Parser { json, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != json.len() {
+        return Err(parser.error("trailing characters"));
+    }
+
+    let runs = value
+        .get("runs")
+        .and_then(Json::as_array)
+        .ok_or("expected an object with a `runs` array")?;
+    runs.iter()
+        .map(|run| {
+            let name = run.get("name").and_then(Json::as_str);
+            let n = run.get("n").and_then(Json::as_f64);
+            let nanos = run.get("nanos").and_then(Json::as_array);
+            let (Some(name), Some(n), Some(nanos)) = (name, n, nanos) else {
+                return Err("every run needs `name`, `n` and `nanos`".to_string());
+            };
+            let nanos = nanos
+                .iter()
+                .map(|x| x.as_f64().ok_or("`nanos` must hold numbers"))
+                .collect::<Result<_, _>>()?;
+            Ok(BenchSamples {
+                name: name.to_string(),
+                n: n as usize,
+                nanos,
+            })
+        })
+        .collect()
+}
+
+// Two-sided p-value of the Mann–Whitney U test that neither sample tends to be
+// larger than the other, from the normal approximation with tie correction.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> f64 {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+
+    let mut all: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect();
+    all.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Tied values share the mean of their ranks.
+    let mut rank_sum_a = 0.0;
+    let mut ties = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let j = i + all[i..].iter().take_while(|x| x.0 == all[i].0).count();
+        let rank = (i + j + 1) as f64 / 2.0;
+        rank_sum_a += rank * all[i..j].iter().filter(|x| x.1).count() as f64;
+        let t = (j - i) as f64;
+        ties += t * t * t - t;
+        i = j;
+    }
+
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let n = n1 + n2;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    // Continuity correction.
+    let z = ((u - n1 * n2 / 2.0).abs() - 0.5).max(0.0) / variance.sqrt();
+    erfc(z / core::f64::consts::SQRT_2)
+}
+
+// Abramowitz and Stegun 7.1.26, absolute error below 1.5e-7.
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    poly * (-x * x).exp()
+}
+
+pub fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => f64::NAN,
+        len if len.is_multiple_of(2) => (sorted[mid - 1] + sorted[mid]) / 2.0,
+        _ => sorted[mid],
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+enum Json {
+    Null,
+    Bool,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+// Just enough JSON for the files `samples_to_json` writes and hand edits of
+// them: no `\u` escapes.
+struct Parser<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                let mut fields = vec![];
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        self.expect(b':')?;
+                        fields.push((key, self.value()?));
+                        if !self.eat(b',') {
+                            self.expect(b'}')?;
+                            break;
+                        }
+                    }
+                }
+                Ok(Json::Object(fields))
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = vec![];
+                if !self.eat(b']') {
+                    loop {
+                        items.push(self.value()?);
+                        if !self.eat(b',') {
+                            self.expect(b']')?;
+                            break;
+                        }
+                    }
+                }
+                Ok(Json::Array(items))
+            }
+            Some(b'"') => Ok(Json::String(self.string()?)),
+            Some(_) => {
+                let rest = &self.json[self.pos..];
+                let len = rest
+                    .find(|c: char| matches!(c, ',' | ']' | '}') || c.is_whitespace())
+                    .unwrap_or(rest.len());
+                let token = &rest[..len];
+                let value = match token {
+                    "null" => Json::Null,
+                    "true" | "false" => Json::Bool,
+                    _ => Json::Number(
+                        token
+                            .parse()
+                            .map_err(|_| self.error(&format!("invalid value `{token}`")))?,
+                    ),
+                };
+                self.pos += len;
+                Ok(value)
+            }
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if !self.eat(b'"') {
+            return Err(self.error("expected a string"));
+        }
+        let mut s = String::new();
+        let mut chars = self.json[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(s);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => s.push('\n'),
+                    Some((_, 't')) => s.push('\t'),
+                    Some((_, c @ ('"' | '\\' | '/'))) => s.push(c),
+                    _ => return Err(self.error("unsupported escape")),
+                },
+                c => s.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.json[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.json.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", byte as char)))
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{message} at byte {}", self.pos)
+    }
+}
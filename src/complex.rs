@@ -0,0 +1,205 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::chunked_reduce;
+use crate::double_double::{dd_combine, dd_reduce, dd_step, non_finite_sum};
+use crate::two_sum;
+#[cfg(feature = "alloc")]
+use crate::{BLOCK, chunked_reduce_512_to_8, fold_partials};
+
+// Complex values are interleaved as [re0, im0, re1, im1, ...]. With 16 lanes,
+// the even lanes only ever see real parts and the odd lanes imaginary parts,
+// so the real kernels are reused unchanged and the parts are only separated
+// when the lanes are combined.
+pub fn complex_sum(values: &[f64]) -> (f64, f64) {
+    assert_interleaved(values.len());
+    let (remainder, mut s) = chunked_reduce(values);
+    for &[re, im] in remainder.as_chunks::<2>().0 {
+        s[0] += re;
+        s[1] += im;
+    }
+    sum_8_to_2(&s)
+}
+
+#[cfg(feature = "alloc")]
+pub fn complex_fold_sum(values: &[f64]) -> (f64, f64) {
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return complex_sum(values);
+    }
+    assert_interleaved(len);
+
+    let capacity = len.div_ceil(BLOCK);
+    let mut re_partials = Vec::with_capacity(capacity);
+    let mut im_partials = Vec::with_capacity(capacity);
+
+    let (chunks, remainder) = values.as_chunks::<BLOCK>();
+    for chunk in chunks {
+        let (re, im) = sum_8_to_2(&chunked_reduce_512_to_8(chunk));
+        re_partials.push(re);
+        im_partials.push(im);
+    }
+
+    if !remainder.is_empty() {
+        let (re, im) = complex_sum(remainder);
+        re_partials.push(re);
+        im_partials.push(im);
+    }
+
+    (fold_partials(re_partials), fold_partials(im_partials))
+}
+
+// Double-double accumulation as in `dd_sum`, for each part separately.
+pub fn complex_dd_sum(values: &[f64]) -> (f64, f64) {
+    assert_interleaved(values.len());
+    let (remainder, mut s, mut c) = dd_reduce(values);
+    for &[re, im] in remainder.as_chunks::<2>().0 {
+        dd_step(&mut s[0], &mut c[0], re);
+        dd_step(&mut s[1], &mut c[1], im);
+    }
+
+    let mut re_lanes = [(0.0, 0.0); 8];
+    let mut im_lanes = [(0.0, 0.0); 8];
+    for i in 0..8 {
+        re_lanes[i] = two_sum(s[2 * i], c[2 * i]);
+        im_lanes[i] = two_sum(s[2 * i + 1], c[2 * i + 1]);
+    }
+    let re = non_finite_sum(s.iter().copied().step_by(2));
+    let im = non_finite_sum(s.iter().copied().skip(1).step_by(2));
+    (
+        re.unwrap_or_else(|| dd_combine(re_lanes).0),
+        im.unwrap_or_else(|| dd_combine(im_lanes).0),
+    )
+}
+
+pub fn complex_sum_f32(values: &[f32]) -> (f32, f32) {
+    assert_interleaved(values.len());
+    let (remainder, mut s) = chunked_reduce_f32(values);
+    for &[re, im] in remainder.as_chunks::<2>().0 {
+        s[0] += re;
+        s[1] += im;
+    }
+    ((s[0] + s[4]) + (s[2] + s[6]), (s[1] + s[5]) + (s[3] + s[7]))
+}
+
+#[cfg(feature = "alloc")]
+pub fn complex_fold_sum_f32(values: &[f32]) -> (f32, f32) {
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return complex_sum_f32(values);
+    }
+    assert_interleaved(len);
+
+    let capacity = len.div_ceil(BLOCK);
+    let mut re_partials = Vec::with_capacity(capacity);
+    let mut im_partials = Vec::with_capacity(capacity);
+    for chunk in values.chunks(BLOCK) {
+        let (re, im) = complex_sum_f32(chunk);
+        re_partials.push(re);
+        im_partials.push(im);
+    }
+
+    (
+        fold_partials_f32(re_partials),
+        fold_partials_f32(im_partials),
+    )
+}
+
+#[cfg(feature = "alloc")]
+fn fold_partials_f32(mut partials: Vec<f32>) -> f32 {
+    while partials.len() >= BLOCK * BLOCK / 2 {
+        partials = partials.chunks(BLOCK).map(chunked_sum_f32).collect();
+    }
+    chunked_sum_f32(&partials)
+}
+
+#[cfg(feature = "alloc")]
+fn chunked_sum_f32(values: &[f32]) -> f32 {
+    let (remainder, s) = chunked_reduce_f32(values);
+    let s = [s[0] + s[4], s[1] + s[5], s[2] + s[6], s[3] + s[7]];
+    (s[0] + s[2]) + (s[1] + s[3]) + remainder.iter().sum::<f32>()
+}
+
+#[inline]
+fn assert_interleaved(len: usize) {
+    assert!(
+        len.is_multiple_of(2),
+        "interleaved complex data must have an even length, got {len}"
+    );
+}
+
+#[inline]
+fn sum_8_to_2(s: &[f64; 8]) -> (f64, f64) {
+    ((s[0] + s[4]) + (s[2] + s[6]), (s[1] + s[5]) + (s[3] + s[7]))
+}
+
+fn chunked_reduce_f32(values: &[f32]) -> (&[f32], [f32; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for &[
+        x0,
+        x1,
+        x2,
+        x3,
+        x4,
+        x5,
+        x6,
+        x7,
+        x8,
+        x9,
+        x10,
+        x11,
+        x12,
+        x13,
+        x14,
+        x15,
+    ] in chunks
+    {
+        s0 += x0;
+        s1 += x1;
+        s2 += x2;
+        s3 += x3;
+        s4 += x4;
+        s5 += x5;
+        s6 += x6;
+        s7 += x7;
+        s8 += x8;
+        s9 += x9;
+        s10 += x10;
+        s11 += x11;
+        s12 += x12;
+        s13 += x13;
+        s14 += x14;
+        s15 += x15;
+    }
+
+    (
+        remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
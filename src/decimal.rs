@@ -0,0 +1,242 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+
+// Fixed-point decimal: `units * 10^-scale`. Sums of decimals are exact integer
+// additions, so monetary totals never pick up binary rounding error; overflow
+// of the i128 is reported instead of wrapping. Equality is by value, so 1.0
+// (10 at scale 1) equals 1.00 (100 at scale 2).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Decimal {
+    pub units: i128,
+    pub scale: u32,
+}
+
+impl Decimal {
+    pub const fn new(units: i128, scale: u32) -> Self {
+        Self { units, scale }
+    }
+
+    // The exact binary value of `x`, rounded half to even to `scale` decimal
+    // places. `None` for NaN, infinities and values that do not fit.
+    pub fn from_f64(x: f64, scale: u32) -> Option<Self> {
+        if !x.is_finite() {
+            return None;
+        }
+
+        // |x| = significand * 2^exponent
+        let bits = x.to_bits();
+        let field = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        let (significand, exponent) = if field == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), field - 1075)
+        };
+
+        let magnitude = scaled_magnitude(significand, exponent, scale)?;
+        let units = i128::try_from(magnitude).ok()?;
+        Some(Self::new(if x < 0.0 { -units } else { units }, scale))
+    }
+
+    // The same value with more decimal places; `None` if `scale` is smaller
+    // than the current one or the units overflow.
+    pub fn rescale(self, scale: u32) -> Option<Self> {
+        let factor = 10i128.checked_pow(scale.checked_sub(self.scale)?)?;
+        Some(Self::new(self.units.checked_mul(factor)?, scale))
+    }
+
+    // The same value at the smallest scale that holds it; zero at scale 0.
+    fn normalized(self) -> Self {
+        if self.units == 0 {
+            return Self::new(0, 0);
+        }
+        let mut normalized = self;
+        while normalized.scale > 0 && normalized.units % 10 == 0 {
+            normalized.units /= 10;
+            normalized.scale -= 1;
+        }
+        normalized
+    }
+
+    // Exact sum at the larger of the two scales.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let (a, b) = (self.rescale(scale)?, other.rescale(scale)?);
+        Some(Self::new(a.units.checked_add(b.units)?, scale))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.normalized(), other.normalized());
+        a.units == b.units && a.scale == b.scale
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.units.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.units < 0 { "-" } else { "" };
+        let magnitude = self.units.unsigned_abs();
+        let Some(divisor) = 10u128.checked_pow(self.scale) else {
+            // More places than any i128 has digits: the integer part is zero.
+            let width = self.scale as usize;
+            return write!(f, "{sign}0.{magnitude:0>width$}");
+        };
+        if self.scale == 0 {
+            return write!(f, "{sign}{magnitude}");
+        }
+        let width = self.scale as usize;
+        write!(
+            f,
+            "{sign}{}.{:0>width$}",
+            magnitude / divisor,
+            magnitude % divisor
+        )
+    }
+}
+
+// Parses `[+-]digits[.digits]`; the scale is the number of fractional digits.
+#[cfg(feature = "alloc")]
+impl FromStr for Decimal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid decimal '{s}'");
+
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut units: i128 = 0;
+        for byte in integer.bytes().chain(fraction.bytes()) {
+            if !byte.is_ascii_digit() {
+                return Err(invalid());
+            }
+            units = units
+                .checked_mul(10)
+                .and_then(|units| units.checked_add((byte - b'0') as i128))
+                .ok_or_else(|| format!("decimal '{s}' does not fit in 128 bits"))?;
+        }
+
+        Ok(Self::new(
+            if negative { -units } else { units },
+            fraction.len() as u32,
+        ))
+    }
+}
+
+// Enough 64-bit limbs for significand * 5^scale at every scale below
+// `MAX_SCALE`: 2^53 * 5^361 is below 2^892.
+const LIMBS: usize = 14;
+
+// From this scale on, even the smallest subnormal, 2^-1074, is more than 2^127
+// units.
+const MAX_SCALE: u32 = 362;
+
+// significand * 2^exponent * 10^scale, rounded half to even, if it is below
+// 2^127. The product is exact: 10^scale = 5^scale * 2^scale, and
+// significand * 5^scale is computed in limbs, so only the final shift rounds.
+fn scaled_magnitude(significand: u64, exponent: i32, scale: u32) -> Option<u128> {
+    if significand == 0 {
+        return Some(0);
+    }
+    if scale >= MAX_SCALE {
+        return None;
+    }
+
+    let mut limbs = [0u64; LIMBS];
+    limbs[0] = significand;
+    let mut len = 1;
+    for _ in 0..scale {
+        let mut carry = 0;
+        for limb in &mut limbs[..len] {
+            let product = *limb as u128 * 5 + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        if carry != 0 {
+            limbs[len] = carry as u64;
+            len += 1;
+        }
+    }
+    let bits = len * 64 - limbs[len - 1].leading_zeros() as usize;
+
+    let shift = exponent as i64 + scale as i64;
+    if shift >= 0 {
+        if bits as i64 + shift > 127 {
+            return None;
+        }
+        let value = limbs[0] as u128 | (limbs[1] as u128) << 64;
+        return Some(value << shift);
+    }
+
+    let shift = shift.unsigned_abs() as usize;
+    if bits > shift + 127 {
+        return None;
+    }
+    let quotient = limb_bits(&limbs, shift) as u128 | (limb_bits(&limbs, shift + 64) as u128) << 64;
+    let half = limb_bits(&limbs, shift - 1) & 1 == 1;
+    let below = shift - 1;
+    let sticky = limbs[..(below / 64).min(LIMBS)]
+        .iter()
+        .any(|&limb| limb != 0)
+        || limb_bits(&limbs, below / 64 * 64) & ((1 << (below % 64)) - 1) != 0;
+    if half && (sticky || quotient & 1 == 1) {
+        // quotient < 2^127, so this cannot overflow.
+        Some(quotient + 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+// The 64 bits of `limbs` from bit `start` on, zero past the end.
+fn limb_bits(limbs: &[u64; LIMBS], start: usize) -> u64 {
+    let (limb, offset) = (start / 64, start % 64);
+    let low = limbs.get(limb).map_or(0, |&limb| limb >> offset);
+    let high = match limbs.get(limb + 1) {
+        Some(&next) if offset > 0 => next << (64 - offset),
+        _ => 0,
+    };
+    low | high
+}
+
+// Exact total at the largest scale of the inputs; `None` on overflow.
+pub fn decimal_sum(values: &[Decimal]) -> Option<Decimal> {
+    let scale = values.iter().map(|value| value.scale).max().unwrap_or(0);
+    let mut sum: i128 = 0;
+    for value in values {
+        sum = sum.checked_add(value.rescale(scale)?.units)?;
+    }
+    Some(Decimal::new(sum, scale))
+}
+
+// Every value is rounded to `scale` places first, as a ledger would store it,
+// and the rounded values are then added exactly.
+pub fn decimal_sum_f64(values: &[f64], scale: u32) -> Option<Decimal> {
+    let mut sum: i128 = 0;
+    for &x in values {
+        sum = sum.checked_add(Decimal::from_f64(x, scale)?.units)?;
+    }
+    Some(Decimal::new(sum, scale))
+}
@@ -0,0 +1,152 @@
+use crate::{fast_two_sum, two_sum};
+
+// Every lane holds a double-double (hi, lo): the rounding error of each hi
+// update is recovered with TwoSum and collected in lo. The lanes are combined
+// pairwise as double-doubles and the result is renormalised, so hi is the
+// correctly rounded value of hi + lo.
+pub fn dd_sum(values: &[f64]) -> (f64, f64) {
+    let (remainder, mut s, mut c) = dd_reduce(values);
+    for &x in remainder {
+        dd_step(&mut s[0], &mut c[0], x);
+    }
+    if let Some(sum) = non_finite_sum(s.into_iter()) {
+        return (sum, 0.0);
+    }
+
+    let mut lanes = [(0.0, 0.0); 16];
+    for (lane, (s, c)) in lanes.iter_mut().zip(s.into_iter().zip(c)) {
+        *lane = two_sum(s, c);
+    }
+    dd_combine(lanes)
+}
+
+pub fn dd_sum_f64(values: &[f64]) -> f64 {
+    dd_sum(values).0
+}
+
+// TwoSum of an infinite lane leaves inf - inf = NaN in its error term, so when
+// a lane is infinite or NaN the plain sum of the lanes is returned instead,
+// which propagates infinities and NaN like the other kernels.
+pub(crate) fn non_finite_sum(lanes: impl Iterator<Item = f64> + Clone) -> Option<f64> {
+    if lanes.clone().all(f64::is_finite) {
+        None
+    } else {
+        Some(lanes.sum())
+    }
+}
+
+// Pairwise double-double reduction; N must be a power of two. Finite lanes
+// can still overflow when they are combined, and TwoSum then turns the
+// infinity into NaN, so the plain sum of the lanes is returned instead.
+pub(crate) fn dd_combine<const N: usize>(mut lanes: [(f64, f64); N]) -> (f64, f64) {
+    let hi = lanes.map(|(hi, _)| hi);
+    let mut width = N;
+    while width > 1 {
+        width /= 2;
+        for i in 0..width {
+            lanes[i] = dd_add(lanes[i], lanes[i + width]);
+            if !lanes[i].0.is_finite() {
+                return (hi.into_iter().sum(), 0.0);
+            }
+        }
+    }
+    lanes[0]
+}
+
+#[inline(always)]
+pub(crate) fn dd_step(s: &mut f64, c: &mut f64, x: f64) {
+    let (t, e) = two_sum(*s, x);
+    *s = t;
+    *c += e;
+}
+
+#[inline(always)]
+fn dd_add((a_hi, a_lo): (f64, f64), (b_hi, b_lo): (f64, f64)) -> (f64, f64) {
+    let (s, e) = two_sum(a_hi, b_hi);
+    let (t, f) = two_sum(a_lo, b_lo);
+    let (s, e) = fast_two_sum(s, e + t);
+    fast_two_sum(s, e + f)
+}
+
+pub(crate) fn dd_reduce(values: &[f64]) -> (&[f64], [f64; 16], [f64; 16]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+    let mut c0 = 0.0;
+    let mut c1 = 0.0;
+    let mut c2 = 0.0;
+    let mut c3 = 0.0;
+    let mut c4 = 0.0;
+    let mut c5 = 0.0;
+    let mut c6 = 0.0;
+    let mut c7 = 0.0;
+    let mut c8 = 0.0;
+    let mut c9 = 0.0;
+    let mut c10 = 0.0;
+    let mut c11 = 0.0;
+    let mut c12 = 0.0;
+    let mut c13 = 0.0;
+    let mut c14 = 0.0;
+    let mut c15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for &[
+        x0,
+        x1,
+        x2,
+        x3,
+        x4,
+        x5,
+        x6,
+        x7,
+        x8,
+        x9,
+        x10,
+        x11,
+        x12,
+        x13,
+        x14,
+        x15,
+    ] in chunks
+    {
+        dd_step(&mut s0, &mut c0, x0);
+        dd_step(&mut s1, &mut c1, x1);
+        dd_step(&mut s2, &mut c2, x2);
+        dd_step(&mut s3, &mut c3, x3);
+        dd_step(&mut s4, &mut c4, x4);
+        dd_step(&mut s5, &mut c5, x5);
+        dd_step(&mut s6, &mut c6, x6);
+        dd_step(&mut s7, &mut c7, x7);
+        dd_step(&mut s8, &mut c8, x8);
+        dd_step(&mut s9, &mut c9, x9);
+        dd_step(&mut s10, &mut c10, x10);
+        dd_step(&mut s11, &mut c11, x11);
+        dd_step(&mut s12, &mut c12, x12);
+        dd_step(&mut s13, &mut c13, x13);
+        dd_step(&mut s14, &mut c14, x14);
+        dd_step(&mut s15, &mut c15, x15);
+    }
+
+    (
+        remainder,
+        [
+            s0, s1, s2, s3, s4, s5, s6, s7, s8, s9, s10, s11, s12, s13, s14, s15,
+        ],
+        [
+            c0, c1, c2, c3, c4, c5, c6, c7, c8, c9, c10, c11, c12, c13, c14, c15,
+        ],
+    )
+}
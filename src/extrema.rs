@@ -0,0 +1,502 @@
+use core::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NanPolicy {
+    // IEEE 754-2019 `minimum`/`maximum`: any NaN makes the result NaN, and
+    // -0.0 orders below +0.0. The arg variants return the first NaN.
+    Propagate,
+    // IEEE 754-2008 `minNum`/`maxNum`: NaN is skipped. Only NaN input gives
+    // `None`.
+    Ignore,
+}
+
+pub fn min(values: &[f64], nan: NanPolicy) -> Option<f64> {
+    match nan {
+        NanPolicy::Propagate => extreme(values, minimum),
+        NanPolicy::Ignore => extreme(values, f64::min).filter(|x| !x.is_nan()),
+    }
+}
+
+pub fn max(values: &[f64], nan: NanPolicy) -> Option<f64> {
+    match nan {
+        NanPolicy::Propagate => extreme(values, maximum),
+        NanPolicy::Ignore => extreme(values, f64::max).filter(|x| !x.is_nan()),
+    }
+}
+
+pub fn min_max(values: &[f64], nan: NanPolicy) -> Option<(f64, f64)> {
+    match nan {
+        NanPolicy::Propagate => extremes(values, minimum, maximum),
+        NanPolicy::Ignore => extremes(values, f64::min, f64::max).filter(|(x, _)| !x.is_nan()),
+    }
+}
+
+// Ties go to the lowest index, and -0.0 orders below +0.0 under both
+// policies, so that under `Propagate` the value at the index is `min`'s bit
+// for bit. (`f64::min` may return either zero, so under `Ignore` it is only
+// equal to it.)
+pub fn argmin(values: &[f64], nan: NanPolicy) -> Option<usize> {
+    match nan {
+        NanPolicy::Propagate => {
+            arg_extreme(values, |x, s| !s.is_nan() && (below(x, s) || x.is_nan()))
+        }
+        NanPolicy::Ignore => arg_extreme(values, |x, s| below(x, s) || (s.is_nan() && !x.is_nan()))
+            .filter(|&i| !values[i].is_nan()),
+    }
+}
+
+pub fn argmax(values: &[f64], nan: NanPolicy) -> Option<usize> {
+    match nan {
+        NanPolicy::Propagate => {
+            arg_extreme(values, |x, s| !s.is_nan() && (below(s, x) || x.is_nan()))
+        }
+        NanPolicy::Ignore => arg_extreme(values, |x, s| below(s, x) || (s.is_nan() && !x.is_nan()))
+            .filter(|&i| !values[i].is_nan()),
+    }
+}
+
+// a < b, with -0.0 below +0.0; false if either is NaN.
+#[inline(always)]
+fn below(a: f64, b: f64) -> bool {
+    a < b || (a == b && a.is_sign_negative() && b.is_sign_positive())
+}
+
+#[inline(always)]
+fn minimum(a: f64, b: f64) -> f64 {
+    match a.partial_cmp(&b) {
+        Some(Ordering::Less) => a,
+        Some(Ordering::Greater) => b,
+        Some(Ordering::Equal) => {
+            if a.is_sign_negative() {
+                a
+            } else {
+                b
+            }
+        }
+        None => f64::NAN,
+    }
+}
+
+#[inline(always)]
+fn maximum(a: f64, b: f64) -> f64 {
+    match a.partial_cmp(&b) {
+        Some(Ordering::Less) => b,
+        Some(Ordering::Greater) => a,
+        Some(Ordering::Equal) => {
+            if a.is_sign_negative() {
+                b
+            } else {
+                a
+            }
+        }
+        None => f64::NAN,
+    }
+}
+
+// The lanes start from the first element, which every op here leaves
+// unchanged, so no identity value is needed.
+#[inline(always)]
+fn extreme(values: &[f64], op: impl Fn(f64, f64) -> f64 + Copy) -> Option<f64> {
+    let &first = values.first()?;
+    let (remainder, s) = extreme_reduce(values, first, op);
+    let s = [
+        op(s[0], s[4]),
+        op(s[1], s[5]),
+        op(s[2], s[6]),
+        op(s[3], s[7]),
+    ];
+    let s = op(op(s[0], s[2]), op(s[1], s[3]));
+    Some(remainder.iter().fold(s, |s, &x| op(s, x)))
+}
+
+#[inline(always)]
+fn extremes(
+    values: &[f64],
+    min_op: impl Fn(f64, f64) -> f64 + Copy,
+    max_op: impl Fn(f64, f64) -> f64 + Copy,
+) -> Option<(f64, f64)> {
+    let &first = values.first()?;
+    let (remainder, lo, hi) = extremes_reduce(values, first, min_op, max_op);
+    let lo = [
+        min_op(lo[0], lo[4]),
+        min_op(lo[1], lo[5]),
+        min_op(lo[2], lo[6]),
+        min_op(lo[3], lo[7]),
+    ];
+    let lo = min_op(min_op(lo[0], lo[2]), min_op(lo[1], lo[3]));
+    let hi = [
+        max_op(hi[0], hi[4]),
+        max_op(hi[1], hi[5]),
+        max_op(hi[2], hi[6]),
+        max_op(hi[3], hi[7]),
+    ];
+    let hi = max_op(max_op(hi[0], hi[2]), max_op(hi[1], hi[3]));
+    Some(
+        remainder
+            .iter()
+            .fold((lo, hi), |(lo, hi), &x| (min_op(lo, x), max_op(hi, x))),
+    )
+}
+
+// `better(x, s)` says whether `x` at a later index replaces the current `s`.
+// Between lanes the indices are not ordered, so a candidate neither better nor
+// worse wins only if its index is lower.
+#[inline(always)]
+fn arg_extreme(values: &[f64], better: impl Fn(f64, f64) -> bool + Copy) -> Option<usize> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let (remainder, s, i) = arg_extreme_reduce(values, better);
+    let (mut best, mut best_index) = (s[0], i[0]);
+    for (s, i) in s.into_iter().zip(i).skip(1) {
+        if better(s, best) || (!better(best, s) && i < best_index) {
+            best = s;
+            best_index = i;
+        }
+    }
+
+    let offset = values.len() - remainder.len();
+    for (i, &x) in remainder.iter().enumerate() {
+        if better(x, best) {
+            best = x;
+            best_index = offset + i;
+        }
+    }
+    Some(best_index)
+}
+
+#[inline(always)]
+fn extreme_reduce(values: &[f64], init: f64, op: impl Fn(f64, f64) -> f64) -> (&[f64], [f64; 8]) {
+    let mut s0 = init;
+    let mut s1 = init;
+    let mut s2 = init;
+    let mut s3 = init;
+    let mut s4 = init;
+    let mut s5 = init;
+    let mut s6 = init;
+    let mut s7 = init;
+    let mut s8 = init;
+    let mut s9 = init;
+    let mut s10 = init;
+    let mut s11 = init;
+    let mut s12 = init;
+    let mut s13 = init;
+    let mut s14 = init;
+    let mut s15 = init;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for &[
+        c0,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5,
+        c6,
+        c7,
+        c8,
+        c9,
+        c10,
+        c11,
+        c12,
+        c13,
+        c14,
+        c15,
+    ] in chunks
+    {
+        s0 = op(s0, c0);
+        s1 = op(s1, c1);
+        s2 = op(s2, c2);
+        s3 = op(s3, c3);
+        s4 = op(s4, c4);
+        s5 = op(s5, c5);
+        s6 = op(s6, c6);
+        s7 = op(s7, c7);
+        s8 = op(s8, c8);
+        s9 = op(s9, c9);
+        s10 = op(s10, c10);
+        s11 = op(s11, c11);
+        s12 = op(s12, c12);
+        s13 = op(s13, c13);
+        s14 = op(s14, c14);
+        s15 = op(s15, c15);
+    }
+
+    (
+        remainder,
+        [
+            op(s0, s8),
+            op(s1, s9),
+            op(s2, s10),
+            op(s3, s11),
+            op(s4, s12),
+            op(s5, s13),
+            op(s6, s14),
+            op(s7, s15),
+        ],
+    )
+}
+
+#[inline(always)]
+fn extremes_reduce(
+    values: &[f64],
+    init: f64,
+    min_op: impl Fn(f64, f64) -> f64,
+    max_op: impl Fn(f64, f64) -> f64,
+) -> (&[f64], [f64; 8], [f64; 8]) {
+    let mut lo0 = init;
+    let mut lo1 = init;
+    let mut lo2 = init;
+    let mut lo3 = init;
+    let mut lo4 = init;
+    let mut lo5 = init;
+    let mut lo6 = init;
+    let mut lo7 = init;
+    let mut lo8 = init;
+    let mut lo9 = init;
+    let mut lo10 = init;
+    let mut lo11 = init;
+    let mut lo12 = init;
+    let mut lo13 = init;
+    let mut lo14 = init;
+    let mut lo15 = init;
+    let mut hi0 = init;
+    let mut hi1 = init;
+    let mut hi2 = init;
+    let mut hi3 = init;
+    let mut hi4 = init;
+    let mut hi5 = init;
+    let mut hi6 = init;
+    let mut hi7 = init;
+    let mut hi8 = init;
+    let mut hi9 = init;
+    let mut hi10 = init;
+    let mut hi11 = init;
+    let mut hi12 = init;
+    let mut hi13 = init;
+    let mut hi14 = init;
+    let mut hi15 = init;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for &[
+        c0,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5,
+        c6,
+        c7,
+        c8,
+        c9,
+        c10,
+        c11,
+        c12,
+        c13,
+        c14,
+        c15,
+    ] in chunks
+    {
+        lo0 = min_op(lo0, c0);
+        lo1 = min_op(lo1, c1);
+        lo2 = min_op(lo2, c2);
+        lo3 = min_op(lo3, c3);
+        lo4 = min_op(lo4, c4);
+        lo5 = min_op(lo5, c5);
+        lo6 = min_op(lo6, c6);
+        lo7 = min_op(lo7, c7);
+        lo8 = min_op(lo8, c8);
+        lo9 = min_op(lo9, c9);
+        lo10 = min_op(lo10, c10);
+        lo11 = min_op(lo11, c11);
+        lo12 = min_op(lo12, c12);
+        lo13 = min_op(lo13, c13);
+        lo14 = min_op(lo14, c14);
+        lo15 = min_op(lo15, c15);
+        hi0 = max_op(hi0, c0);
+        hi1 = max_op(hi1, c1);
+        hi2 = max_op(hi2, c2);
+        hi3 = max_op(hi3, c3);
+        hi4 = max_op(hi4, c4);
+        hi5 = max_op(hi5, c5);
+        hi6 = max_op(hi6, c6);
+        hi7 = max_op(hi7, c7);
+        hi8 = max_op(hi8, c8);
+        hi9 = max_op(hi9, c9);
+        hi10 = max_op(hi10, c10);
+        hi11 = max_op(hi11, c11);
+        hi12 = max_op(hi12, c12);
+        hi13 = max_op(hi13, c13);
+        hi14 = max_op(hi14, c14);
+        hi15 = max_op(hi15, c15);
+    }
+
+    (
+        remainder,
+        [
+            min_op(lo0, lo8),
+            min_op(lo1, lo9),
+            min_op(lo2, lo10),
+            min_op(lo3, lo11),
+            min_op(lo4, lo12),
+            min_op(lo5, lo13),
+            min_op(lo6, lo14),
+            min_op(lo7, lo15),
+        ],
+        [
+            max_op(hi0, hi8),
+            max_op(hi1, hi9),
+            max_op(hi2, hi10),
+            max_op(hi3, hi11),
+            max_op(hi4, hi12),
+            max_op(hi5, hi13),
+            max_op(hi6, hi14),
+            max_op(hi7, hi15),
+        ],
+    )
+}
+
+#[inline(always)]
+fn arg_extreme_reduce(
+    values: &[f64],
+    better: impl Fn(f64, f64) -> bool,
+) -> (&[f64], [f64; 16], [usize; 16]) {
+    let (chunks, remainder) = values.as_chunks::<16>();
+    let Some((first, chunks)) = chunks.split_first() else {
+        let s = [values[0]; 16];
+        return (&values[1..], s, [0; 16]);
+    };
+
+    let mut s0 = first[0];
+    let mut s1 = first[1];
+    let mut s2 = first[2];
+    let mut s3 = first[3];
+    let mut s4 = first[4];
+    let mut s5 = first[5];
+    let mut s6 = first[6];
+    let mut s7 = first[7];
+    let mut s8 = first[8];
+    let mut s9 = first[9];
+    let mut s10 = first[10];
+    let mut s11 = first[11];
+    let mut s12 = first[12];
+    let mut s13 = first[13];
+    let mut s14 = first[14];
+    let mut s15 = first[15];
+    let mut i0 = 0;
+    let mut i1 = 1;
+    let mut i2 = 2;
+    let mut i3 = 3;
+    let mut i4 = 4;
+    let mut i5 = 5;
+    let mut i6 = 6;
+    let mut i7 = 7;
+    let mut i8 = 8;
+    let mut i9 = 9;
+    let mut i10 = 10;
+    let mut i11 = 11;
+    let mut i12 = 12;
+    let mut i13 = 13;
+    let mut i14 = 14;
+    let mut i15 = 15;
+
+    for (
+        n,
+        &[
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ],
+    ) in chunks.iter().enumerate()
+    {
+        let base = (n + 1) * 16;
+        if better(c0, s0) {
+            s0 = c0;
+            i0 = base;
+        }
+        if better(c1, s1) {
+            s1 = c1;
+            i1 = base + 1;
+        }
+        if better(c2, s2) {
+            s2 = c2;
+            i2 = base + 2;
+        }
+        if better(c3, s3) {
+            s3 = c3;
+            i3 = base + 3;
+        }
+        if better(c4, s4) {
+            s4 = c4;
+            i4 = base + 4;
+        }
+        if better(c5, s5) {
+            s5 = c5;
+            i5 = base + 5;
+        }
+        if better(c6, s6) {
+            s6 = c6;
+            i6 = base + 6;
+        }
+        if better(c7, s7) {
+            s7 = c7;
+            i7 = base + 7;
+        }
+        if better(c8, s8) {
+            s8 = c8;
+            i8 = base + 8;
+        }
+        if better(c9, s9) {
+            s9 = c9;
+            i9 = base + 9;
+        }
+        if better(c10, s10) {
+            s10 = c10;
+            i10 = base + 10;
+        }
+        if better(c11, s11) {
+            s11 = c11;
+            i11 = base + 11;
+        }
+        if better(c12, s12) {
+            s12 = c12;
+            i12 = base + 12;
+        }
+        if better(c13, s13) {
+            s13 = c13;
+            i13 = base + 13;
+        }
+        if better(c14, s14) {
+            s14 = c14;
+            i14 = base + 14;
+        }
+        if better(c15, s15) {
+            s15 = c15;
+            i15 = base + 15;
+        }
+    }
+
+    (
+        remainder,
+        [
+            s0, s1, s2, s3, s4, s5, s6, s7, s8, s9, s10, s11, s12, s13, s14, s15,
+        ],
+        [
+            i0, i1, i2, i3, i4, i5, i6, i7, i8, i9, i10, i11, i12, i13, i14, i15,
+        ],
+    )
+}
@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::path::Path;
+use std::str::FromStr;
+use std::string::String;
+use std::{format, vec};
+
+use crate::FoldSum;
+
+const READ_BYTES: usize = 1 << 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dtype {
+    F32,
+    F64,
+}
+
+impl Dtype {
+    pub fn size(self) -> usize {
+        match self {
+            Dtype::F32 => 4,
+            Dtype::F64 => 8,
+        }
+    }
+}
+
+impl FromStr for Dtype {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f32" => Ok(Dtype::F32),
+            "f64" => Ok(Dtype::F64),
+            _ => Err(format!("unknown dtype `{s}`, expected `f32` or `f64`")),
+        }
+    }
+}
+
+pub fn sum_file(path: impl AsRef<Path>, dtype: Dtype) -> io::Result<f64> {
+    sum_reader(File::open(path)?, dtype)
+}
+
+// Sums raw little-endian values, widening f32 to f64, with the same result as
+// `fold_sum` over the whole array. The input is read in large chunks and
+// decoded into an aligned buffer, so neither the file length nor the read
+// sizes need to line up with the element size; an element split between two
+// reads is carried over. Trailing bytes that do not make a whole element are
+// an error.
+pub fn sum_reader(mut reader: impl Read, dtype: Dtype) -> io::Result<f64> {
+    let size = dtype.size();
+    let mut bytes = vec![0u8; READ_BYTES];
+    let mut values = vec![0.0; READ_BYTES / size];
+    let mut sum = FoldSum::new();
+    let mut carry = 0;
+
+    loop {
+        let read = match reader.read(&mut bytes[carry..]) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        let filled = carry + read;
+        let len = filled / size;
+        decode(&bytes[..len * size], &mut values[..len], dtype);
+        sum.add_slice(&values[..len]);
+
+        bytes.copy_within(len * size..filled, 0);
+        carry = filled - len * size;
+    }
+
+    if carry != 0 {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("{carry} trailing bytes do not make a whole {size}-byte value"),
+        ));
+    }
+    Ok(sum.sum())
+}
+
+fn decode(bytes: &[u8], values: &mut [f64], dtype: Dtype) {
+    match dtype {
+        Dtype::F32 => {
+            for (value, bytes) in values.iter_mut().zip(bytes.as_chunks::<4>().0) {
+                *value = f32::from_le_bytes(*bytes) as f64;
+            }
+        }
+        Dtype::F64 => {
+            for (value, bytes) in values.iter_mut().zip(bytes.as_chunks::<8>().0) {
+                *value = f64::from_le_bytes(*bytes);
+            }
+        }
+    }
+}
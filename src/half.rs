@@ -0,0 +1,240 @@
+use crate::sum_8_to_1;
+
+pub fn f16_sum_f32(values: &[u16]) -> f32 {
+    let (remainder, mut s) = half_reduce_f32(values, f16x16_to_f32);
+    s[0] += remainder.iter().map(|&x| f16_to_f32(x)).sum::<f32>();
+    sum_8_to_1_f32(&s)
+}
+
+pub fn f16_sum_f64(values: &[u16]) -> f64 {
+    let (remainder, mut s) = half_reduce_f64(values, f16x16_to_f32);
+    s[0] += remainder.iter().map(|&x| f16_to_f32(x) as f64).sum::<f64>();
+    sum_8_to_1(&s)
+}
+
+pub fn bf16_sum_f32(values: &[u16]) -> f32 {
+    let (remainder, mut s) = half_reduce_f32(values, bf16x16_to_f32);
+    s[0] += remainder.iter().map(|&x| bf16_to_f32(x)).sum::<f32>();
+    sum_8_to_1_f32(&s)
+}
+
+pub fn bf16_sum_f64(values: &[u16]) -> f64 {
+    let (remainder, mut s) = half_reduce_f64(values, bf16x16_to_f32);
+    s[0] += remainder
+        .iter()
+        .map(|&x| bf16_to_f32(x) as f64)
+        .sum::<f64>();
+    sum_8_to_1(&s)
+}
+
+#[inline(always)]
+pub fn bf16_to_f32(x: u16) -> f32 {
+    f32::from_bits((x as u32) << 16)
+}
+
+// Branch-free: the exponent and mantissa are moved into f32 position and
+// rebiased by a multiplication, which also normalises subnormals. Only
+// infinity and NaN, whose exponent is all ones, need their exponent patched.
+#[inline(always)]
+pub fn f16_to_f32(x: u16) -> f32 {
+    let sign = ((x & 0x8000) as u32) << 16;
+    let bits = ((x & 0x7fff) as u32) << 13;
+    let magnitude = f32::from_bits(bits) * f32::from_bits(0x7780_0000); // 2^112
+    let magnitude = if bits >= 0x0f80_0000 {
+        f32::from_bits(bits | 0x7f80_0000)
+    } else {
+        magnitude
+    };
+    f32::from_bits(magnitude.to_bits() | sign)
+}
+
+#[inline(always)]
+fn bf16x16_to_f32(values: &[u16; 16]) -> [f32; 16] {
+    values.map(bf16_to_f32)
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "f16c"))]
+#[inline(always)]
+fn f16x16_to_f32(values: &[u16; 16]) -> [f32; 16] {
+    use core::arch::x86_64::{__m128i, _mm_loadu_si128, _mm256_cvtph_ps, _mm256_storeu_ps};
+
+    let mut out = [0.0; 16];
+    let (halves, _) = values.as_chunks::<8>();
+    let (outs, _) = out.as_chunks_mut::<8>();
+    for (half, out) in halves.iter().zip(outs) {
+        // SAFETY: `f16c` is enabled at compile time and both pointers are
+        // valid for 8 elements; the loads and stores are unaligned.
+        unsafe {
+            let half = _mm_loadu_si128(half.as_ptr().cast::<__m128i>());
+            _mm256_storeu_ps(out.as_mut_ptr(), _mm256_cvtph_ps(half));
+        }
+    }
+    out
+}
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "f16c")))]
+#[inline(always)]
+fn f16x16_to_f32(values: &[u16; 16]) -> [f32; 16] {
+    values.map(f16_to_f32)
+}
+
+#[inline]
+fn sum_8_to_1_f32(values: &[f32; 8]) -> f32 {
+    let values = [
+        values[0] + values[4],
+        values[1] + values[5],
+        values[2] + values[6],
+        values[3] + values[7],
+    ];
+    (values[0] + values[2]) + (values[1] + values[3])
+}
+
+#[inline(always)]
+fn half_reduce_f32(
+    values: &[u16],
+    convert: impl Fn(&[u16; 16]) -> [f32; 16],
+) -> (&[u16], [f32; 8]) {
+    let mut s0: f32 = 0.0;
+    let mut s1: f32 = 0.0;
+    let mut s2: f32 = 0.0;
+    let mut s3: f32 = 0.0;
+    let mut s4: f32 = 0.0;
+    let mut s5: f32 = 0.0;
+    let mut s6: f32 = 0.0;
+    let mut s7: f32 = 0.0;
+    let mut s8: f32 = 0.0;
+    let mut s9: f32 = 0.0;
+    let mut s10: f32 = 0.0;
+    let mut s11: f32 = 0.0;
+    let mut s12: f32 = 0.0;
+    let mut s13: f32 = 0.0;
+    let mut s14: f32 = 0.0;
+    let mut s15: f32 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for chunk in chunks {
+        let [
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ] = convert(chunk);
+        s0 += c0;
+        s1 += c1;
+        s2 += c2;
+        s3 += c3;
+        s4 += c4;
+        s5 += c5;
+        s6 += c6;
+        s7 += c7;
+        s8 += c8;
+        s9 += c9;
+        s10 += c10;
+        s11 += c11;
+        s12 += c12;
+        s13 += c13;
+        s14 += c14;
+        s15 += c15;
+    }
+
+    (
+        remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
+
+#[inline(always)]
+fn half_reduce_f64(
+    values: &[u16],
+    convert: impl Fn(&[u16; 16]) -> [f32; 16],
+) -> (&[u16], [f64; 8]) {
+    let mut s0: f64 = 0.0;
+    let mut s1: f64 = 0.0;
+    let mut s2: f64 = 0.0;
+    let mut s3: f64 = 0.0;
+    let mut s4: f64 = 0.0;
+    let mut s5: f64 = 0.0;
+    let mut s6: f64 = 0.0;
+    let mut s7: f64 = 0.0;
+    let mut s8: f64 = 0.0;
+    let mut s9: f64 = 0.0;
+    let mut s10: f64 = 0.0;
+    let mut s11: f64 = 0.0;
+    let mut s12: f64 = 0.0;
+    let mut s13: f64 = 0.0;
+    let mut s14: f64 = 0.0;
+    let mut s15: f64 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for chunk in chunks {
+        let [
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ] = convert(chunk);
+        s0 += c0 as f64;
+        s1 += c1 as f64;
+        s2 += c2 as f64;
+        s3 += c3 as f64;
+        s4 += c4 as f64;
+        s5 += c5 as f64;
+        s6 += c6 as f64;
+        s7 += c7 as f64;
+        s8 += c8 as f64;
+        s9 += c9 as f64;
+        s10 += c10 as f64;
+        s11 += c11 as f64;
+        s12 += c12 as f64;
+        s13 += c13 as f64;
+        s14 += c14 as f64;
+        s15 += c15 as f64;
+    }
+
+    (
+        remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
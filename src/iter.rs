@@ -0,0 +1,191 @@
+use crate::{BLOCK, chunked_sum, chunked_sum_512_to_1, sum_8_to_1};
+
+const LEVELS: usize = 4;
+
+pub trait SumExt: Iterator<Item = f64> + Sized {
+    // Same result as `chunked_sum` over the collected items.
+    fn fast_sum(self) -> f64 {
+        let mut lanes = [0.0; 16];
+        let mut chunk = [0.0; 16];
+        let mut len = 0;
+
+        for x in self {
+            chunk[len] = x;
+            len += 1;
+            if len == 16 {
+                add_lanes(&mut lanes, &chunk);
+                len = 0;
+            }
+        }
+
+        lanes_sum(&lanes, &chunk[..len])
+    }
+
+    // Same result as `fold_sum` over the collected items.
+    fn fold_sum(self) -> f64 {
+        let mut sum = FoldSum::new();
+        let mut block = [0.0; BLOCK];
+        let mut len = 0;
+
+        for x in self {
+            block[len] = x;
+            len += 1;
+            if len == BLOCK {
+                sum.add_slice(&block);
+                len = 0;
+            }
+        }
+
+        sum.add_slice(&block[..len]);
+        sum.sum()
+    }
+}
+
+impl<I: Iterator<Item = f64>> SumExt for I {}
+
+// Streaming accumulator with the same result as `fold_sum` over everything
+// added, however it was split into `add`/`add_slice` calls, and without
+// allocating.
+//
+// `fold_sum` picks its tree from the total length, which a stream does not
+// know in advance. So each level keeps both candidates: the `chunked_sum`
+// lanes over all its items, for as long as it could still end up below
+// BLOCK²/2 items, and the BLOCK-sized partials it feeds to the next level.
+// `sum` then takes whichever `fold_sum` would have. Only beyond
+// BLOCK^LEVELS * BLOCK²/2 values does the top level keep summing into its
+// lanes where `fold_sum` would add another level.
+#[derive(Clone, Debug)]
+pub struct FoldSum {
+    levels: [Level; LEVELS],
+}
+
+#[derive(Clone, Debug)]
+struct Level {
+    block: [f64; BLOCK],
+    len: usize,
+    count: usize,
+    lanes: [f64; 16],
+}
+
+impl Default for FoldSum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FoldSum {
+    pub fn new() -> Self {
+        let level = Level {
+            block: [0.0; BLOCK],
+            len: 0,
+            count: 0,
+            lanes: [0.0; 16],
+        };
+        Self {
+            levels: [level.clone(), level.clone(), level.clone(), level],
+        }
+    }
+
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.push(0, x);
+    }
+
+    pub fn add_slice(&mut self, mut values: &[f64]) {
+        while self.levels[0].len != 0 && !values.is_empty() {
+            self.push(0, values[0]);
+            values = &values[1..];
+        }
+
+        let (chunks, remainder) = values.as_chunks::<BLOCK>();
+        for chunk in chunks {
+            self.push_block(0, chunk);
+        }
+
+        for &x in remainder {
+            self.push(0, x);
+        }
+    }
+
+    pub fn sum(&self) -> f64 {
+        let mut levels = self.levels.clone();
+        finish(&mut levels, 0)
+    }
+
+    #[inline]
+    fn push(&mut self, level: usize, x: f64) {
+        let current = &mut self.levels[level];
+        current.block[current.len] = x;
+        current.len += 1;
+        if current.len == BLOCK {
+            current.len = 0;
+            let block = current.block;
+            self.push_block(level, &block);
+        }
+    }
+
+    fn push_block(&mut self, level: usize, block: &[f64; BLOCK]) {
+        let top = level == LEVELS - 1;
+        let current = &mut self.levels[level];
+        if top || current.count < BLOCK * BLOCK / 2 {
+            for chunk in block.as_chunks::<16>().0 {
+                add_lanes(&mut current.lanes, chunk);
+            }
+        }
+        current.count += BLOCK;
+
+        if !top {
+            self.push(level + 1, chunked_sum_512_to_1(block));
+        }
+    }
+}
+
+fn finish(levels: &mut [Level; LEVELS], level: usize) -> f64 {
+    let current = &mut levels[level];
+    let block = &current.block[..current.len];
+
+    if level == LEVELS - 1 || current.count + current.len < BLOCK * BLOCK / 2 {
+        let (chunks, remainder) = block.as_chunks::<16>();
+        for chunk in chunks {
+            add_lanes(&mut current.lanes, chunk);
+        }
+        return lanes_sum(&current.lanes, remainder);
+    }
+
+    // The last partial of the level below can have filled this block; a full
+    // block is summed as a chunk, like `fold_partials` does.
+    if !block.is_empty() {
+        let partial = match block.as_array::<BLOCK>() {
+            Some(block) => chunked_sum_512_to_1(block),
+            None => chunked_sum(block),
+        };
+        let next = &mut levels[level + 1];
+        next.block[next.len] = partial;
+        next.len += 1;
+    }
+    finish(levels, level + 1)
+}
+
+#[inline(always)]
+fn add_lanes(lanes: &mut [f64; 16], chunk: &[f64; 16]) {
+    for (s, &c) in lanes.iter_mut().zip(chunk) {
+        *s += c;
+    }
+}
+
+// The tail of `chunked_sum`, given its 16 lanes and remainder.
+#[inline(always)]
+fn lanes_sum(lanes: &[f64; 16], remainder: &[f64]) -> f64 {
+    let mut s = [
+        lanes[0] + lanes[8],
+        lanes[1] + lanes[9],
+        lanes[2] + lanes[10],
+        lanes[3] + lanes[11],
+        lanes[4] + lanes[12],
+        lanes[5] + lanes[13],
+        lanes[6] + lanes[14],
+        lanes[7] + lanes[15],
+    ];
+    s[0] += remainder.iter().sum::<f64>();
+    sum_8_to_1(&s)
+}
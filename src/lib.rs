@@ -1,3 +1,94 @@
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+mod aligned;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod compare;
+mod complex;
+mod decimal;
+mod double_double;
+mod extrema;
+#[cfg(feature = "std")]
+mod file;
+mod half;
+mod iter;
+#[cfg(feature = "std")]
+mod log_sum_exp;
+#[cfg(feature = "alloc")]
+mod matrix;
+mod norms;
+#[cfg(feature = "alloc")]
+mod prefetch;
+mod product;
+#[cfg(feature = "alloc")]
+mod registry;
+mod reproducible;
+mod strided;
+#[cfg(feature = "std")]
+mod text;
+#[cfg(feature = "alloc")]
+mod weighted;
+
+#[cfg(feature = "alloc")]
+pub use aligned::{AlignedBuffer, Alignment};
+#[cfg(feature = "parquet")]
+pub use arrow::sum_parquet_column;
+#[cfg(feature = "arrow")]
+pub use arrow::{sum_arrow_array, sum_arrow_arrays};
+#[cfg(feature = "std")]
+pub use cache::CacheSizes;
+#[cfg(feature = "std")]
+pub use compare::{BenchSamples, mann_whitney_u, median, parse_samples, samples_to_json};
+pub use complex::{complex_dd_sum, complex_sum, complex_sum_f32};
+#[cfg(feature = "alloc")]
+pub use complex::{complex_fold_sum, complex_fold_sum_f32};
+pub use decimal::{Decimal, decimal_sum, decimal_sum_f64};
+pub use double_double::{dd_sum, dd_sum_f64};
+pub use extrema::{NanPolicy, argmax, argmin, max, min, min_max};
+#[cfg(feature = "std")]
+pub use file::{Dtype, sum_file, sum_reader};
+pub use half::{bf16_sum_f32, bf16_sum_f64, bf16_to_f32, f16_sum_f32, f16_sum_f64, f16_to_f32};
+pub use iter::{FoldSum, SumExt};
+#[cfg(feature = "std")]
+pub use log_sum_exp::log_sum_exp;
+#[cfg(feature = "alloc")]
+pub use matrix::{Layout, col_sums, row_sums};
+#[cfg(feature = "alloc")]
+pub use norms::l1_norm;
+pub use norms::linf_norm;
+#[cfg(feature = "std")]
+pub use norms::{l2_norm, p_norm};
+#[cfg(feature = "alloc")]
+pub use prefetch::{Prefetch, prefetch_fold_sum, prefetch_fold_sum_with};
+pub use product::{product, product_exp};
+#[cfg(feature = "alloc")]
+pub use registry::{
+    Accuracy, AtScale, Entry, Gathered, Input, Interleaved, Kernel, Operation, Power, REGISTRY,
+    Scratch, Strided, Summer, Weighted, WithNanPolicy, kernel, kernels,
+};
+#[cfg(feature = "alloc")]
+pub use reproducible::exact_sum;
+pub use reproducible::{ReproducibleSum, reproducible_sum};
+pub use strided::{sum_gather, sum_strided};
+#[cfg(feature = "std")]
+pub use text::{Delimiter, TextOptions, TextSum, TextSummary, sum_text};
+#[cfg(feature = "alloc")]
+pub use weighted::{weighted_mean, weighted_sum};
+#[cfg(feature = "std")]
+pub use weighted::{weighted_mean_eft, weighted_mean_fma, weighted_sum_eft, weighted_sum_fma};
+
 #[inline]
 pub fn for_sum(values: &[f64]) -> f64 {
     let mut s = 0.0;
@@ -14,42 +105,176 @@ pub fn iter_sum(values: &[f64]) -> f64 {
 
 pub const BLOCK: usize = 512;
 
+#[cfg(feature = "alloc")]
 pub fn fold_sum(values: &[f64]) -> f64 {
     let len = values.len();
     if len < BLOCK * BLOCK / 2 {
         return chunked_sum(values);
     }
 
-    let capacity_current = (len + BLOCK - 1) / BLOCK;
+    let capacity_current = len.div_ceil(BLOCK);
     let mut buffer_current = Vec::with_capacity(capacity_current);
-    let capacity_next = (capacity_current + BLOCK - 1) / BLOCK;
-    let mut buffer_next = Vec::with_capacity(capacity_next);
 
     let (chunks, remainder) = values.as_chunks::<BLOCK>();
-    buffer_current.extend(chunks.iter().map(|chunk| chunked_sum_512_to_1(chunk)));
+    buffer_current.extend(chunks.iter().map(chunked_sum_512_to_1));
 
     if !remainder.is_empty() {
         buffer_current.push(chunked_sum(remainder));
         // buffer_current.push(remainder.iter().sum());
     }
 
+    fold_partials(buffer_current)
+}
+
+#[cfg(feature = "alloc")]
+fn fold_partials(mut buffer_current: Vec<f64>) -> f64 {
+    let capacity_next = buffer_current.len().div_ceil(BLOCK);
+    let mut buffer_next = Vec::with_capacity(capacity_next);
+
     while buffer_current.len() >= BLOCK * BLOCK / 2 {
         buffer_next.clear();
 
         let (chunks, remainder) = buffer_current.as_chunks::<BLOCK>();
-        buffer_next.extend(chunks.iter().map(|chunk| chunked_sum_512_to_1(chunk)));
+        buffer_next.extend(chunks.iter().map(chunked_sum_512_to_1));
 
         if !remainder.is_empty() {
             buffer_next.push(chunked_sum(remainder));
             // buffer_next.push(remainder.iter().sum());
         }
 
-        std::mem::swap(&mut buffer_current, &mut buffer_next);
+        core::mem::swap(&mut buffer_current, &mut buffer_next);
     }
 
     chunked_sum(&buffer_current)
 }
 
+pub fn fold_sum_scratch_len(len: usize) -> usize {
+    if len < BLOCK * BLOCK / 2 {
+        return 0;
+    }
+
+    let capacity_current = len.div_ceil(BLOCK);
+    capacity_current + capacity_current.div_ceil(BLOCK)
+}
+
+// Same result as `fold_sum`, with the partials kept in `scratch` instead of
+// two `Vec`s. `scratch` needs at least `fold_sum_scratch_len(values.len())`
+// elements.
+pub fn fold_sum_with_scratch(values: &[f64], scratch: &mut [f64]) -> f64 {
+    scratch_fold(values, scratch, chunked_sum_512_to_1)
+}
+
+// Same result as `expanded_fold_sum`, see `fold_sum_with_scratch`.
+pub fn expanded_fold_sum_with_scratch(values: &[f64], scratch: &mut [f64]) -> f64 {
+    scratch_fold(values, scratch, expanded_sum_512_to_1)
+}
+
+#[inline(always)]
+fn scratch_fold(values: &[f64], scratch: &mut [f64], sum_512_to_1: fn(&[f64; 512]) -> f64) -> f64 {
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return chunked_sum(values);
+    }
+
+    let needed = fold_sum_scratch_len(len);
+    assert!(
+        scratch.len() >= needed,
+        "scratch too small: {} values need fold_sum_scratch_len = {needed}, got {}",
+        len,
+        scratch.len()
+    );
+    let (mut buffer_current, mut buffer_next) = scratch.split_at_mut(len.div_ceil(BLOCK));
+
+    let mut len_current = sums_512_to_1_into(values, buffer_current, sum_512_to_1);
+
+    while len_current >= BLOCK * BLOCK / 2 {
+        let len_next =
+            sums_512_to_1_into(&buffer_current[..len_current], buffer_next, sum_512_to_1);
+
+        core::mem::swap(&mut buffer_current, &mut buffer_next);
+        len_current = len_next;
+    }
+
+    chunked_sum(&buffer_current[..len_current])
+}
+
+#[inline(always)]
+fn sums_512_to_1_into(
+    values: &[f64],
+    out: &mut [f64],
+    sum_512_to_1: fn(&[f64; 512]) -> f64,
+) -> usize {
+    let (chunks, remainder) = values.as_chunks::<BLOCK>();
+    for (out, chunk) in out.iter_mut().zip(chunks) {
+        *out = sum_512_to_1(chunk);
+    }
+
+    if !remainder.is_empty() {
+        out[chunks.len()] = chunked_sum(remainder);
+    }
+
+    values.len().div_ceil(BLOCK)
+}
+
+const STACK_LEVELS: usize = 4;
+
+// Allocation-free fold over `STACK_LEVELS` fixed levels of BLOCK partials on
+// the stack. A level is folded into the next one as soon as it is full, so the
+// tree is built bottom-up while streaming: the result has the same accuracy as
+// `fold_sum` but can differ from it in the last bits.
+pub fn stack_fold_sum(values: &[f64]) -> f64 {
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return chunked_sum(values);
+    }
+
+    let mut levels = [[0.0; BLOCK]; STACK_LEVELS];
+    let mut counts = [0; STACK_LEVELS];
+
+    let (chunks, remainder) = values.as_chunks::<BLOCK>();
+    for chunk in chunks {
+        push_partial(&mut levels, &mut counts, 0, chunked_sum_512_to_1(chunk));
+    }
+
+    if !remainder.is_empty() {
+        push_partial(&mut levels, &mut counts, 0, chunked_sum(remainder));
+    }
+
+    for level in 0..STACK_LEVELS - 1 {
+        if counts[level] > 0 {
+            let partial = chunked_sum(&levels[level][..counts[level]]);
+            push_partial(&mut levels, &mut counts, level + 1, partial);
+        }
+    }
+
+    chunked_sum(&levels[STACK_LEVELS - 1][..counts[STACK_LEVELS - 1]])
+}
+
+#[inline]
+fn push_partial(
+    levels: &mut [[f64; BLOCK]; STACK_LEVELS],
+    counts: &mut [usize; STACK_LEVELS],
+    mut level: usize,
+    mut partial: f64,
+) {
+    loop {
+        levels[level][counts[level]] = partial;
+        counts[level] += 1;
+        if counts[level] < BLOCK {
+            return;
+        }
+
+        partial = chunked_sum_512_to_1(&levels[level]);
+        counts[level] = 0;
+        if level == STACK_LEVELS - 1 {
+            // Only reached after BLOCK^(STACK_LEVELS + 1) values; the top
+            // level restarts from its own total.
+            continue;
+        }
+        level += 1;
+    }
+}
+
 #[inline]
 pub fn chunked_sum(values: &[f64]) -> f64 {
     let (remainder, mut s) = chunked_reduce(values);
@@ -68,6 +293,40 @@ fn sum_8_to_1(values: &[f64; 8]) -> f64 {
     (values[0] + values[2]) + (values[1] + values[3])
 }
 
+#[inline(always)]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let z = s - a;
+    (s, (a - (s - z)) + (b - z))
+}
+
+// Exact for exponents in the normal range, -1022..=1023.
+#[inline(always)]
+fn pow2(exponent: i32) -> f64 {
+    f64::from_bits(((exponent + 1023) as u64) << 52)
+}
+
+// `mantissa * 2^exponent`, rounded once, saturating to zero or infinity.
+fn ldexp(mantissa: f64, exponent: i64) -> f64 {
+    let exponent = exponent.clamp(-1100, 1100);
+    let half = exponent / 2;
+    mantissa * pow2(half as i32) * pow2((exponent - half) as i32)
+}
+
+// Requires |a| >= |b| or a == 0.
+#[inline(always)]
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+#[cfg(feature = "std")]
+#[inline(always)]
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
 fn chunked_reduce(values: &[f64]) -> (&[f64], [f64; 8]) {
     let mut s0 = 0.0;
     let mut s1 = 0.0;
@@ -213,6 +472,185 @@ fn chunked_reduce_512_to_8(values: &[f64; 512]) -> [f64; 8] {
     ]
 }
 
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn map_fold_sum(values: &[f64], f: impl Fn(f64) -> f64 + Copy) -> f64 {
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return map_chunked_sum(values, f);
+    }
+
+    let capacity_current = len.div_ceil(BLOCK);
+    let mut buffer_current = Vec::with_capacity(capacity_current);
+
+    let (chunks, remainder) = values.as_chunks::<BLOCK>();
+    buffer_current.extend(chunks.iter().map(|chunk| map_sum_512_to_1(chunk, f)));
+
+    if !remainder.is_empty() {
+        buffer_current.push(map_chunked_sum(remainder, f));
+    }
+
+    fold_partials(buffer_current)
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn map_chunked_sum(values: &[f64], f: impl Fn(f64) -> f64 + Copy) -> f64 {
+    let (remainder, mut s) = map_reduce(values, f);
+    s[0] += remainder.iter().map(|&x| f(x)).sum::<f64>();
+    sum_8_to_1(&s)
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn map_reduce(values: &[f64], f: impl Fn(f64) -> f64) -> (&[f64], [f64; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for &[
+        c0,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5,
+        c6,
+        c7,
+        c8,
+        c9,
+        c10,
+        c11,
+        c12,
+        c13,
+        c14,
+        c15,
+    ] in chunks
+    {
+        s0 += f(c0);
+        s1 += f(c1);
+        s2 += f(c2);
+        s3 += f(c3);
+        s4 += f(c4);
+        s5 += f(c5);
+        s6 += f(c6);
+        s7 += f(c7);
+        s8 += f(c8);
+        s9 += f(c9);
+        s10 += f(c10);
+        s11 += f(c11);
+        s12 += f(c12);
+        s13 += f(c13);
+        s14 += f(c14);
+        s15 += f(c15);
+    }
+
+    (
+        remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn map_sum_512_to_1(values: &[f64; 512], f: impl Fn(f64) -> f64) -> f64 {
+    let values = map_reduce_512_to_8(values, f);
+    sum_8_to_1(&values)
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn map_reduce_512_to_8(values: &[f64; 512], f: impl Fn(f64) -> f64) -> [f64; 8] {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, _remainder) = values.as_chunks::<16>();
+    for &[
+        c0,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5,
+        c6,
+        c7,
+        c8,
+        c9,
+        c10,
+        c11,
+        c12,
+        c13,
+        c14,
+        c15,
+    ] in chunks
+    {
+        s0 += f(c0);
+        s1 += f(c1);
+        s2 += f(c2);
+        s3 += f(c3);
+        s4 += f(c4);
+        s5 += f(c5);
+        s6 += f(c6);
+        s7 += f(c7);
+        s8 += f(c8);
+        s9 += f(c9);
+        s10 += f(c10);
+        s11 += f(c11);
+        s12 += f(c12);
+        s13 += f(c13);
+        s14 += f(c14);
+        s15 += f(c15);
+    }
+
+    [
+        s0 + s8,
+        s1 + s9,
+        s2 + s10,
+        s3 + s11,
+        s4 + s12,
+        s5 + s13,
+        s6 + s14,
+        s7 + s15,
+    ]
+}
+
 pub fn wide_sum_fold0(values: &[f64]) -> f64 {
     let mut s0 = 0.0;
     let mut s1 = 0.0;
@@ -417,19 +855,20 @@ pub fn wide_sum_fold2(values: &[f64]) -> f64 {
     r + s[0] + s[1] + s[2] + s[3]
 }
 
+#[cfg(feature = "alloc")]
 pub fn expanded_fold_sum(values: &[f64]) -> f64 {
     let len = values.len();
     if len < BLOCK * BLOCK / 2 {
         return chunked_sum(values);
     }
 
-    let capacity_current = (len + BLOCK - 1) / BLOCK;
+    let capacity_current = len.div_ceil(BLOCK);
     let mut buffer_current = Vec::with_capacity(capacity_current);
-    let capacity_next = (capacity_current + BLOCK - 1) / BLOCK;
+    let capacity_next = capacity_current.div_ceil(BLOCK);
     let mut buffer_next = Vec::with_capacity(capacity_next);
 
     let (chunks, remainder) = values.as_chunks::<BLOCK>();
-    buffer_current.extend(chunks.iter().map(|chunk| expanded_sum_512_to_1(chunk)));
+    buffer_current.extend(chunks.iter().map(expanded_sum_512_to_1));
 
     if !remainder.is_empty() {
         buffer_current.push(chunked_sum(remainder));
@@ -439,13 +878,13 @@ pub fn expanded_fold_sum(values: &[f64]) -> f64 {
         buffer_next.clear();
 
         let (chunks, remainder) = buffer_current.as_chunks::<BLOCK>();
-        buffer_next.extend(chunks.iter().map(|chunk| expanded_sum_512_to_1(chunk)));
+        buffer_next.extend(chunks.iter().map(expanded_sum_512_to_1));
 
         if !remainder.is_empty() {
             buffer_next.push(chunked_sum(remainder));
         }
 
-        std::mem::swap(&mut buffer_current, &mut buffer_next);
+        core::mem::swap(&mut buffer_current, &mut buffer_next);
     }
 
     chunked_sum(&buffer_current)
@@ -972,7 +1411,7 @@ fn expanded_reduce_512_to_8(values: &[f64; 512]) -> [f64; 8] {
         ]
     };
 
-    let values = {
+    {
         let mut s0 = 0.0;
         let mut s1 = 0.0;
         let mut s2 = 0.0;
@@ -995,7 +1434,5 @@ fn expanded_reduce_512_to_8(values: &[f64; 512]) -> [f64; 8] {
         }
 
         [s0, s1, s2, s3, s4, s5, s6, s7]
-    };
-
-    values
+    }
 }
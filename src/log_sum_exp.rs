@@ -0,0 +1,15 @@
+use crate::{NanPolicy, map_fold_sum, max};
+
+// Two passes: the maximum is subtracted before exponentiating, so the largest
+// term is exactly 1 and the sum of the rest goes through the same blocked fold
+// as `fold_sum`.
+pub fn log_sum_exp(values: &[f64]) -> f64 {
+    let Some(max) = max(values, NanPolicy::Propagate) else {
+        return f64::NEG_INFINITY;
+    };
+    if !max.is_finite() {
+        return max;
+    }
+
+    max + map_fold_sum(values, |x| (x - max).exp()).ln()
+}
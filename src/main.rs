@@ -1,10 +1,20 @@
-use std::time::Instant;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 use sums::{
-    chunked_sum, expanded_fold_sum, fold_sum, for_sum, iter_sum, wide_sum_fold0, wide_sum_fold1,
-    wide_sum_fold2,
+    Accuracy, BenchSamples, CacheSizes, Delimiter, Dtype, Entry, Gathered, Input, Operation,
+    REGISTRY, Summer, TextOptions, TextSum, kernels, mann_whitney_u, median, parse_samples,
+    samples_to_json, sum_file,
 };
 
+use asm::{LoopReport, asm_functions};
+
+mod asm;
+
 // const ITER: i32 = 20_000;
 // const N: i32 = BLOCK * BLOCK / 2 - 1;
 
@@ -17,7 +27,608 @@ use sums::{
 const ITER: i32 = 5;
 const N: i32 = 200_000_000;
 
-fn main() {
+const USAGE: &str = "usage: sums [FILE [--dtype f32|f64]]  (FILE with a `.` or `/` in it)
+       sums file [--dtype f32|f64] [--] FILE
+       sums kernels
+       sums asm [FILE.s] [--kernel NAME] [--print] [--check]
+       sums roofline [--min-time MS]
+       sums record OUT.json [--samples K] [--sizes N,N,...]
+       sums compare BASELINE.json CURRENT.json [--threshold PCT] [--alpha P]
+       sums text [FILE]... [--column N] [--delimiter C] [--header] [--stats]
+       sums parquet FILE --column NAME  (with the `parquet` feature)";
+
+const SUBCOMMANDS: &[&str] = &[
+    "file",
+    "kernels",
+    "asm",
+    "roofline",
+    "record",
+    "compare",
+    "text",
+    #[cfg(feature = "parquet")]
+    "parquet",
+];
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        None => {
+            bench();
+            Ok(())
+        }
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some("kernels") => {
+            list_kernels();
+            Ok(())
+        }
+        Some("asm") => asm_command(&args[1..]),
+        Some("roofline") => roofline_command(&args[1..]),
+        Some("record") => record_command(&args[1..]),
+        Some("compare") => compare_command(&args[1..]),
+        Some("text") => sum_text_command(&args[1..]),
+        #[cfg(feature = "parquet")]
+        Some("parquet") => sum_parquet_command(&args[1..]),
+        Some("file") => sum_file_command(&args[1..]),
+        // The `sums FILE` form only for what looks like a path or an option,
+        // so that a mistyped subcommand is not opened as a file.
+        Some(arg)
+            if arg.contains(['.', '/', std::path::MAIN_SEPARATOR]) || arg.starts_with('-') =>
+        {
+            sum_file_command(&args)
+        }
+        Some(arg) => Err(format!(
+            "unknown subcommand `{arg}`, expected one of: {}\n\
+             (to sum a file named `{arg}`, use `sums file {arg}`)",
+            SUBCOMMANDS.join(", ")
+        )),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// A FILE without `.` or `/` in its name, or named like an option, is summed
+// with `sums file NAME` or `sums -- NAME`: after `--`, every argument is a path.
+fn sum_file_command(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut dtype = Dtype::F64;
+    let mut options = true;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--" if options => options = false,
+            "--dtype" if options => {
+                let value = args.next().ok_or("--dtype needs a value")?;
+                dtype = value.parse()?;
+            }
+            _ if options && arg.starts_with('-') => {
+                return Err(format!("unknown option `{arg}`\n{USAGE}"));
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+
+    let path = path.ok_or(USAGE)?;
+    let sum = sum_file(path, dtype).map_err(|e| format!("{path}: {e}"))?;
+    println!("{sum}");
+    Ok(())
+}
+
+// Like `awk '{s+=$1} END {print s}'`: numbers one per line, or from a
+// 1-based CSV or whitespace-separated column, read from the files or stdin.
+fn sum_text_command(args: &[String]) -> Result<(), String> {
+    let mut paths = Vec::new();
+    let mut options = TextOptions::default();
+    let mut stats = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--column" => {
+                let value = args.next().ok_or("--column needs a value")?;
+                let column: usize = value
+                    .parse()
+                    .ok()
+                    .filter(|&column| column > 0)
+                    .ok_or_else(|| format!("invalid column `{value}`, columns start at 1"))?;
+                options.column = Some(column - 1);
+            }
+            "--delimiter" => {
+                let value = args.next().ok_or("--delimiter needs a value")?;
+                // As with awk's `-F ' '`, a single space splits on runs of
+                // whitespace.
+                options.delimiter = match value.as_bytes() {
+                    b" " => Delimiter::Whitespace,
+                    [b] => Delimiter::Byte(*b),
+                    _ if value == "\\t" => Delimiter::Byte(b'\t'),
+                    _ => return Err(format!("invalid delimiter `{value}`, expected one byte")),
+                };
+            }
+            "--header" => options.header = true,
+            "--stats" => stats = true,
+            _ => paths.push(arg),
+        }
+    }
+
+    let mut sum = TextSum::new(options);
+    if paths.is_empty() {
+        sum.read(io::stdin().lock())
+            .map_err(|e| format!("stdin: {e}"))?;
+    }
+    for path in paths {
+        let result = if path == "-" {
+            sum.read(io::stdin().lock())
+        } else {
+            File::open(path).and_then(|file| sum.read(BufReader::new(file)))
+        };
+        result.map_err(|e| format!("{path}: {e}"))?;
+    }
+
+    let summary = sum.summary();
+    println!("count: {}", summary.count);
+    println!("sum: {}", summary.sum);
+    if stats {
+        println!("mean: {}", summary.mean());
+        if let Some((min, max)) = summary.min_max {
+            println!("min: {min}");
+            println!("max: {max}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+fn sum_parquet_command(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut column = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--column" => column = Some(args.next().ok_or("--column needs a value")?),
+            _ if options && arg.starts_with('-') => {
+                return Err(format!("unknown option `{arg}`\n{USAGE}"));
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+
+    let (Some(path), Some(column)) = (path, column) else {
+        return Err(USAGE.to_string());
+    };
+    let sum = sums::sum_parquet_column(path, column).map_err(|e| format!("{path}: {e}"))?;
+    println!("{sum}");
+    Ok(())
+}
+
+fn list_kernels() {
+    for entry in REGISTRY {
+        println!(
+            "{:<30} {:<14} {:<17} {}",
+            entry.name(),
+            entry.input(),
+            format!("{:?}", entry.accuracy()),
+            entry.description()
+        );
+    }
+}
+
+// Finds every registered kernel in the assembly that `--emit asm` (see
+// .cargo/config.toml) wrote for this binary and reports its hot loop. With
+// `--check`, fails if a kernel that should use SIMD lanes has no packed adds.
+fn asm_command(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut only = None;
+    let mut print = false;
+    let mut check = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--kernel" => only = Some(args.next().ok_or("--kernel needs a value")?),
+            "--print" => print = true,
+            "--check" => check = true,
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+
+    let path = match path {
+        Some(path) => path,
+        None => {
+            if cfg!(debug_assertions) {
+                eprintln!("warning: this is an unoptimised build, run with --release");
+            }
+            emitted_asm()?
+        }
+    };
+    let asm = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let functions = asm_functions(&asm);
+
+    // Sums and norms in lanes are expected to vectorise; a single running sum,
+    // the integer bins of `reproducible_sum`, and the gather, whose loads are
+    // scalar and bounds-checked, are not. Products and extrema have no adds.
+    let kernels = REGISTRY
+        .iter()
+        .map(|entry| {
+            let expected = matches!(entry.operation(), Operation::Sum | Operation::Norm)
+                && matches!(
+                    entry.accuracy(),
+                    Accuracy::Lanes | Accuracy::Blocked | Accuracy::Compensated
+                )
+                && entry.input() != Gathered::NAME;
+            (entry.name(), expected)
+        })
+        .filter(|(name, _)| only.is_none_or(|only| only == name));
+
+    let mut failed = Vec::new();
+    println!(
+        "{:<20} {:<7} {:>11} {:>11} {:>12}",
+        "kernel", "width", "vector adds", "scalar adds", "loop-carried"
+    );
+    let find = |name: &str| {
+        functions
+            .iter()
+            .find(|function| function.name.rsplit("::").next() == Some(name))
+    };
+    for (name, expected) in kernels {
+        let Some(function) = find(name) else {
+            println!("{name:<20} not found");
+            failed.push(name);
+            continue;
+        };
+
+        // A thin wrapper such as `dd_sum_f64` has its loop in the function it
+        // calls.
+        let report = function.hot_loop().or_else(|| {
+            function
+                .callees()
+                .filter_map(|callee| functions.iter().find(|function| function.name == callee))
+                .find_map(|callee| callee.hot_loop())
+        });
+        match report {
+            Some(LoopReport {
+                vector_bits,
+                vector_adds,
+                scalar_adds,
+                loop_carried,
+            }) => {
+                let width = match vector_bits {
+                    0 => "scalar",
+                    128 => "xmm",
+                    256 => "ymm",
+                    _ => "zmm",
+                };
+                println!(
+                    "{name:<20} {width:<7} {vector_adds:>11} {scalar_adds:>11} {loop_carried:>12}"
+                );
+            }
+            None => println!("{name:<20} no loop with adds"),
+        }
+        if expected && !report.is_some_and(|report| report.is_vectorised()) {
+            failed.push(name);
+        }
+
+        if print {
+            println!("{}:", function.name);
+            for line in &function.lines {
+                println!("{line}");
+            }
+        }
+    }
+
+    if check && !failed.is_empty() {
+        return Err(format!("not vectorised: {}", failed.join(", ")));
+    }
+    Ok(())
+}
+
+// The newest `sums-*.s` in the `deps` directory next to this binary.
+fn emitted_asm() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let deps = exe.with_file_name("deps");
+    let entries = fs::read_dir(&deps).map_err(|e| format!("{}: {e}", deps.display()))?;
+
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("sums-") && name.ends_with(".s")
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+        .ok_or_else(|| format!("no sums-*.s in {}", deps.display()))
+}
+
+// Compares every kernel with the read bandwidth the host achieves for the same
+// input size: half of each cache level, so the data stays resident, and four
+// times the last level, as in STREAM, for main memory.
+fn roofline_command(args: &[String]) -> Result<(), String> {
+    let mut min_time = Duration::from_millis(200);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-time" => {
+                let value = args.next().ok_or("--min-time needs a value")?;
+                let millis = value
+                    .parse()
+                    .map_err(|_| format!("invalid --min-time `{value}`, expected milliseconds"))?;
+                min_time = Duration::from_millis(millis);
+            }
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+
+    let caches = CacheSizes::detect();
+    let levels = [
+        ("L1", caches.l1 / 2),
+        ("L2", caches.l2 / 2),
+        ("L3", caches.l3 / 2),
+        ("memory", caches.l3 * 4),
+    ];
+
+    for (level, bytes) in levels {
+        let n = bytes / size_of::<f64>();
+        let v: Vec<f64> = (1..=n).rev().map(|x| x as f64).collect();
+
+        let roofline = throughput(&v, min_time, |v| {
+            black_box(read_all(v));
+        });
+        println!(
+            "{level} ({} KiB): read {:.1} GB/s",
+            bytes >> 10,
+            roofline / 1e9
+        );
+        for kernel in kernels::<f64, f64>() {
+            let rate = throughput(&v, min_time, |v| {
+                black_box(kernel.sum(v));
+            });
+            println!(
+                "  {:<20} {:>7.1} GB/s {:>5.1}%",
+                kernel.name(),
+                rate / 1e9,
+                100.0 * rate / roofline
+            );
+        }
+    }
+    Ok(())
+}
+
+// Integer adds into 8 vector accumulators. Nothing has to stay in order, so
+// the loop is limited only by the loads; written with intrinsics because the
+// vectorisers reshuffle such a loop into something slower.
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+fn read_all(values: &[f64]) -> u64 {
+    use std::arch::x86_64::{
+        __m256i, _mm256_add_epi64, _mm256_loadu_si256, _mm256_setzero_si256, _mm256_storeu_si256,
+    };
+
+    let (chunks, remainder) = values.as_chunks::<32>();
+    // SAFETY: `avx2` is enabled at compile time and every chunk holds 8 loads
+    // of 4 values; the loads and the store are unaligned.
+    let lanes = unsafe {
+        let mut acc = [_mm256_setzero_si256(); 8];
+        for chunk in chunks {
+            for (acc, lanes) in acc.iter_mut().zip(chunk.as_chunks::<4>().0) {
+                *acc = _mm256_add_epi64(*acc, _mm256_loadu_si256(lanes.as_ptr().cast::<__m256i>()));
+            }
+        }
+        let mut lanes = [0u64; 32];
+        for (acc, out) in acc.iter().zip(lanes.as_chunks_mut::<4>().0) {
+            _mm256_storeu_si256(out.as_mut_ptr().cast::<__m256i>(), *acc);
+        }
+        lanes
+    };
+    lanes
+        .into_iter()
+        .chain(remainder.iter().map(|x| x.to_bits()))
+        .fold(0, u64::wrapping_add)
+}
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx2")))]
+fn read_all(values: &[f64]) -> u64 {
+    values
+        .iter()
+        .fold(0, |acc, x| acc.wrapping_add(x.to_bits()))
+}
+
+// Best bytes per second of `f` over `values` among batches of at least a
+// millisecond, for `min_time` after one warm-up call. Taking the best batch
+// filters out interruptions by other processes.
+fn throughput(values: &[f64], min_time: Duration, f: impl Fn(&[f64])) -> f64 {
+    let values = black_box(values);
+    f(values);
+
+    let start = Instant::now();
+    let mut batch = 1u64;
+    let mut best = 0.0f64;
+    loop {
+        let batch_start = Instant::now();
+        for _ in 0..batch {
+            f(values);
+        }
+        let elapsed = batch_start.elapsed();
+
+        if elapsed < Duration::from_millis(1) {
+            batch *= 2;
+        } else {
+            best = best.max((batch * size_of_val(values) as u64) as f64 / elapsed.as_secs_f64());
+            if start.elapsed() >= min_time {
+                return best;
+            }
+        }
+    }
+}
+
+// Records `--samples` per-call times of every kernel at each size, by default
+// half of each cache level, for `sums compare`.
+fn record_command(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut samples = 30;
+    let mut sizes = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--samples" => {
+                let value = args.next().ok_or("--samples needs a value")?;
+                samples =
+                    value.parse().ok().filter(|&k| k >= 2).ok_or_else(|| {
+                        format!("invalid --samples `{value}`, expected at least 2")
+                    })?;
+            }
+            "--sizes" => {
+                let value = args.next().ok_or("--sizes needs a value")?;
+                let parsed: Result<Vec<usize>, _> = value.split(',').map(str::parse).collect();
+                sizes = Some(parsed.map_err(|_| format!("invalid --sizes `{value}`"))?);
+            }
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+    let path = path.ok_or(USAGE)?;
+    let sizes = sizes.unwrap_or_else(|| {
+        let caches = CacheSizes::detect();
+        [caches.l1, caches.l2, caches.l3]
+            .map(|bytes| bytes / 2 / size_of::<f64>())
+            .to_vec()
+    });
+
+    let mut runs = vec![];
+    for n in sizes {
+        let v: Vec<f64> = (1..=n).rev().map(|x| x as f64).collect();
+        for kernel in kernels::<f64, f64>() {
+            let nanos = sample_nanos(&v, samples, |v| {
+                black_box(kernel.sum(v));
+            });
+            println!("{:<20} {n:>10} {:>12.1} ns", kernel.name(), median(&nanos));
+            runs.push(BenchSamples {
+                name: kernel.name().to_string(),
+                n,
+                nanos,
+            });
+        }
+    }
+    fs::write(path, samples_to_json(&runs)).map_err(|e| format!("{path}: {e}"))
+}
+
+// Compares two `sums record` runs kernel by kernel. A change counts when the
+// medians differ by more than `--threshold` percent and the Mann–Whitney U
+// test rejects equal distributions at `--alpha`; any slower kernel fails.
+fn compare_command(args: &[String]) -> Result<(), String> {
+    let mut paths = vec![];
+    let mut threshold = 5.0;
+    let mut alpha = 0.05;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let value = args.next().ok_or("--threshold needs a value")?;
+                threshold = value
+                    .parse()
+                    .map_err(|_| format!("invalid --threshold `{value}`, expected a percentage"))?;
+            }
+            "--alpha" => {
+                let value = args.next().ok_or("--alpha needs a value")?;
+                alpha = value
+                    .parse()
+                    .map_err(|_| format!("invalid --alpha `{value}`"))?;
+            }
+            _ if paths.len() < 2 && !arg.starts_with('-') => paths.push(arg),
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+    let [baseline, current] = paths[..] else {
+        return Err(USAGE.to_string());
+    };
+    let read = |path: &String| {
+        let json = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        parse_samples(&json).map_err(|e| format!("{path}: {e}"))
+    };
+    let (baseline, current) = (read(baseline)?, read(current)?);
+
+    println!(
+        "{:<20} {:>10} {:>12} {:>12} {:>8} {:>8}",
+        "kernel", "n", "baseline ns", "current ns", "change", "p"
+    );
+    let mut regressions = 0;
+    for run in &current {
+        let Some(base) = baseline
+            .iter()
+            .find(|base| base.name == run.name && base.n == run.n)
+        else {
+            println!("{:<20} {:>10} not in baseline", run.name, run.n);
+            continue;
+        };
+        let (before, after) = (median(&base.nanos), median(&run.nanos));
+        let change = 100.0 * (after / before - 1.0);
+        let p = mann_whitney_u(&base.nanos, &run.nanos);
+        let verdict = match p < alpha {
+            true if change > threshold => {
+                regressions += 1;
+                "regression"
+            }
+            true if change < -threshold => "improvement",
+            _ => "",
+        };
+        println!(
+            "{:<20} {:>10} {before:>12.1} {after:>12.1} {change:>+7.1}% {p:>8.4} {verdict}",
+            run.name, run.n
+        );
+    }
+
+    match regressions {
+        0 => Ok(()),
+        k => Err(format!(
+            "{k} regression(s) above {threshold}% at significance {alpha}"
+        )),
+    }
+}
+
+// `count` per-call times in nanoseconds of `f` over `values`, each the mean of
+// a batch of at least a millisecond, after one warm-up call.
+fn sample_nanos(values: &[f64], count: usize, f: impl Fn(&[f64])) -> Vec<f64> {
+    let values = black_box(values);
+    f(values);
+
+    let mut batch = 1u32;
+    loop {
+        let start = Instant::now();
+        for _ in 0..batch {
+            f(values);
+        }
+        if start.elapsed() >= Duration::from_millis(1) {
+            break;
+        }
+        batch *= 2;
+    }
+
+    (0..count)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..batch {
+                f(values);
+            }
+            start.elapsed().as_nanos() as f64 / batch as f64
+        })
+        .collect()
+}
+
+fn bench() {
     // info
     use std::process::Command;
     let rustc_cpus = Command::new("rustc")
@@ -56,75 +667,13 @@ fn main() {
         values.rev().map(|x| x as f64).collect()
     };
 
-    // bench: for_sum
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += for_sum(&v);
-    }
-    let dur = start.elapsed();
-    println!("for_sum: {:?} (acc = {})", dur, acc);
-
-    // bench: iter_sum
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += iter_sum(&v);
-    }
-    let dur = start.elapsed();
-    println!("iter_sum: {:?} (acc = {})", dur, acc);
-
-    // bench: fold_sum
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += fold_sum(&v);
-    }
-    let dur = start.elapsed();
-    println!("fold_sum: {:?} (acc = {})", dur, acc);
-
-    // bench: chunked_sum
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += chunked_sum(&v);
-    }
-    let dur = start.elapsed();
-    println!("chunked_sum: {:?} (acc = {})", dur, acc);
-
-    // bench: wide_sum_fold0
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += wide_sum_fold0(&v);
-    }
-    let dur = start.elapsed();
-    println!("wide_sum_fold0: {:?} (acc = {})", dur, acc);
-
-    // bench: wide_sum_fold1
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += wide_sum_fold1(&v);
-    }
-    let dur = start.elapsed();
-    println!("wide_sum_fold1: {:?} (acc = {})", dur, acc);
-
-    // bench: wide_sum_fold2
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += wide_sum_fold2(&v);
-    }
-    let dur = start.elapsed();
-    println!("wide_sum_fold2: {:?} (acc = {})", dur, acc);
-
-    // bench: expanded_fold_sum
-    let start = Instant::now();
-    let mut acc = 0.0f64;
-    for _ in 0..ITER {
-        acc += expanded_fold_sum(&v);
+    for kernel in kernels::<f64, f64>() {
+        let start = Instant::now();
+        let mut acc = 0.0f64;
+        for _ in 0..ITER {
+            acc += kernel.sum(&v);
+        }
+        let dur = start.elapsed();
+        println!("{}: {:?} (acc = {})", kernel.name(), dur, acc);
     }
-    let dur = start.elapsed();
-    println!("expanded_fold_sum: {:?} (acc = {})", dur, acc);
 }
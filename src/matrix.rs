@@ -0,0 +1,88 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{BLOCK, fold_sum};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    RowMajor,
+    ColumnMajor,
+}
+
+pub fn row_sums(values: &[f64], rows: usize, cols: usize, layout: Layout) -> Vec<f64> {
+    assert_eq!(
+        Some(values.len()),
+        rows.checked_mul(cols),
+        "{rows} x {cols} matrix"
+    );
+    match layout {
+        Layout::RowMajor => sum_lines(values, rows, cols),
+        Layout::ColumnMajor => sum_across_lines(values, rows),
+    }
+}
+
+pub fn col_sums(values: &[f64], rows: usize, cols: usize, layout: Layout) -> Vec<f64> {
+    assert_eq!(
+        Some(values.len()),
+        rows.checked_mul(cols),
+        "{rows} x {cols} matrix"
+    );
+    match layout {
+        Layout::RowMajor => sum_across_lines(values, cols),
+        Layout::ColumnMajor => sum_lines(values, cols, rows),
+    }
+}
+
+// One sum per contiguous line.
+fn sum_lines(values: &[f64], lines: usize, len: usize) -> Vec<f64> {
+    if len == 0 {
+        return vec![0.0; lines];
+    }
+    values.chunks_exact(len).map(fold_sum).collect()
+}
+
+// One sum per position across all lines. The lines are streamed in order and
+// added element-wise into a row of accumulators, so every access is
+// contiguous. For accuracy on long inputs the accumulators form a tree like
+// `fold_sum`: level 0 takes BLOCK lines, then is flushed into level 1, which
+// takes BLOCK flushes before being flushed into level 2, and so on.
+fn sum_across_lines(values: &[f64], len: usize) -> Vec<f64> {
+    if len == 0 {
+        return Vec::new();
+    }
+
+    let mut levels: Vec<Vec<f64>> = vec![vec![0.0; len]];
+    let mut counts: Vec<usize> = vec![0];
+
+    for line in values.chunks_exact(len) {
+        add_into(&mut levels[0], line);
+        counts[0] += 1;
+
+        let mut level = 0;
+        while counts[level] == BLOCK {
+            if level + 1 == levels.len() {
+                levels.push(vec![0.0; len]);
+                counts.push(0);
+            }
+            let (lower, upper) = levels.split_at_mut(level + 1);
+            add_into(&mut upper[0], &lower[level]);
+            lower[level].fill(0.0);
+            counts[level] = 0;
+            counts[level + 1] += 1;
+            level += 1;
+        }
+    }
+
+    let mut levels = levels.into_iter();
+    let mut sums = levels.next().unwrap();
+    for level in levels {
+        add_into(&mut sums, &level);
+    }
+    sums
+}
+
+#[inline]
+fn add_into(acc: &mut [f64], values: &[f64]) {
+    for (a, &v) in acc.iter_mut().zip(values) {
+        *a += v;
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(feature = "alloc")]
+use crate::map_fold_sum;
+#[cfg(feature = "std")]
+use crate::pow2;
+
+#[cfg(feature = "alloc")]
+pub fn l1_norm(values: &[f64]) -> f64 {
+    map_fold_sum(values, f64::abs)
+}
+
+// Two passes: the first finds the largest magnitude, the second sums squares
+// scaled by the power of two nearest below it, so nothing overflows or
+// underflows prematurely. Scaling by a power of two is exact. Unlike `dnrm2`
+// the update needs no branches, so the second pass vectorises like `fold_sum`.
+#[cfg(feature = "std")]
+pub fn l2_norm(values: &[f64]) -> f64 {
+    let max = linf_norm(values);
+    if max == 0.0 || !max.is_finite() {
+        return max;
+    }
+
+    let exponent = (((max.to_bits() >> 52) & 0x7ff) as i32 - 1023).clamp(-1022, 1022);
+    let scale = pow2(exponent);
+    let inv_scale = pow2(-exponent);
+
+    map_fold_sum(values, |x| {
+        let x = x * inv_scale;
+        x * x
+    })
+    .sqrt()
+        * scale
+}
+
+// NaN propagates: once a lane has seen NaN it keeps it.
+pub fn linf_norm(values: &[f64]) -> f64 {
+    let (remainder, s) = max_abs_reduce(values);
+    let s = [
+        max_abs(s[0], s[4]),
+        max_abs(s[1], s[5]),
+        max_abs(s[2], s[6]),
+        max_abs(s[3], s[7]),
+    ];
+    let s = max_abs(max_abs(s[0], s[2]), max_abs(s[1], s[3]));
+    remainder.iter().fold(s, |s, &x| max_abs(s, x))
+}
+
+// Scaled by the largest magnitude; `p` of 1, 2 and infinity go to the
+// dedicated kernels.
+#[cfg(feature = "std")]
+pub fn p_norm(values: &[f64], p: f64) -> f64 {
+    assert!(p > 0.0, "p must be positive");
+    if p == 1.0 {
+        return l1_norm(values);
+    }
+    if p == 2.0 {
+        return l2_norm(values);
+    }
+    if p == f64::INFINITY {
+        return linf_norm(values);
+    }
+
+    let max = linf_norm(values);
+    if max == 0.0 || !max.is_finite() {
+        return max;
+    }
+
+    // Below 1 so that large p cannot overflow, and the power of two first so
+    // that the reciprocal of a subnormal max does not.
+    let exponent = (((max.to_bits() >> 52) & 0x7ff) as i32 - 1023).clamp(-1022, 1022);
+    let inv_scale = pow2(-exponent);
+    let inv_max = 1.0 / (max * inv_scale);
+    map_fold_sum(values, |x| (x * inv_scale * inv_max).abs().powf(p)).powf(1.0 / p) * max
+}
+
+#[inline(always)]
+fn max_abs(s: f64, x: f64) -> f64 {
+    let x = x.abs();
+    if x > s || x.is_nan() { x } else { s }
+}
+
+fn max_abs_reduce(values: &[f64]) -> (&[f64], [f64; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    for &[
+        c0,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5,
+        c6,
+        c7,
+        c8,
+        c9,
+        c10,
+        c11,
+        c12,
+        c13,
+        c14,
+        c15,
+    ] in chunks
+    {
+        s0 = max_abs(s0, c0);
+        s1 = max_abs(s1, c1);
+        s2 = max_abs(s2, c2);
+        s3 = max_abs(s3, c3);
+        s4 = max_abs(s4, c4);
+        s5 = max_abs(s5, c5);
+        s6 = max_abs(s6, c6);
+        s7 = max_abs(s7, c7);
+        s8 = max_abs(s8, c8);
+        s9 = max_abs(s9, c9);
+        s10 = max_abs(s10, c10);
+        s11 = max_abs(s11, c11);
+        s12 = max_abs(s12, c12);
+        s13 = max_abs(s13, c13);
+        s14 = max_abs(s14, c14);
+        s15 = max_abs(s15, c15);
+    }
+
+    (
+        remainder,
+        [
+            max_abs(s0, s8),
+            max_abs(s1, s9),
+            max_abs(s2, s10),
+            max_abs(s3, s11),
+            max_abs(s4, s12),
+            max_abs(s5, s13),
+            max_abs(s6, s14),
+            max_abs(s7, s15),
+        ],
+    )
+}
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::{BLOCK, chunked_sum, chunked_sum_512_to_1, fold_partials, sum_8_to_1};
+
+// Blocks summed side by side, so that several independent load streams are in
+// flight at once.
+const INTERLEAVE: usize = 4;
+const GROUP: usize = INTERLEAVE * BLOCK;
+
+// Software prefetching for `prefetch_fold_sum_with`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prefetch {
+    // How far ahead of its current position to prefetch each block, in bytes.
+    pub distance: usize,
+    // Prefetch with the non-temporal hint, which keeps the lines out of the
+    // outer caches since every value is read exactly once. Slower than the
+    // default at every distance on the development host.
+    pub non_temporal: bool,
+}
+
+// Eight steps (1 KiB) ahead in every block. On the development host, summing
+// 150M and 200M values from memory, 0.5 to 2 KiB were within a few percent of
+// each other and 5-13% faster than interleaving without prefetches; 256 bytes
+// and 4 KiB lost most of that.
+impl Default for Prefetch {
+    fn default() -> Self {
+        Self {
+            distance: 8 * STEP * size_of::<f64>(),
+            non_temporal: false,
+        }
+    }
+}
+
+// `fold_sum` for inputs far beyond the last-level cache, with the default
+// `Prefetch`; same result as `fold_sum`.
+pub fn prefetch_fold_sum(values: &[f64]) -> f64 {
+    prefetch_fold_sum_with(values, Prefetch::default())
+}
+
+// Sums INTERLEAVE neighbouring blocks of BLOCK at a time, each into its own 16
+// lanes, so that four load streams 4 KiB apart are in flight at once. Each
+// stream prefetches its own memory `prefetch.distance` bytes ahead of its
+// loads. Every block gets the same lanes as in `fold_sum`, so the result is
+// bitwise identical.
+pub fn prefetch_fold_sum_with(values: &[f64], prefetch: Prefetch) -> f64 {
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return chunked_sum(values);
+    }
+
+    let mut partials = Vec::with_capacity(len.div_ceil(BLOCK));
+    let (groups, rest) = values.as_chunks::<GROUP>();
+    if prefetch.non_temporal {
+        sum_groups::<{ NON_TEMPORAL }>(groups, prefetch.distance, &mut partials);
+    } else {
+        sum_groups::<{ TEMPORAL }>(groups, prefetch.distance, &mut partials);
+    }
+
+    let (chunks, remainder) = rest.as_chunks::<BLOCK>();
+    partials.extend(chunks.iter().map(chunked_sum_512_to_1));
+    if !remainder.is_empty() {
+        partials.push(chunked_sum(remainder));
+    }
+
+    fold_partials(partials)
+}
+
+#[inline(always)]
+fn sum_groups<const HINT: i32>(groups: &[[f64; GROUP]], distance: usize, out: &mut Vec<f64>) {
+    for group in groups {
+        out.extend(group_sums::<HINT>(group, distance));
+    }
+}
+
+// Values of each block consumed per step, and the cache lines they span.
+const STEP: usize = 16;
+const LINE: usize = 64;
+const LINES_PER_STEP: usize = STEP * size_of::<f64>() / LINE;
+
+#[inline(always)]
+fn group_sums<const HINT: i32>(group: &[f64; GROUP], distance: usize) -> [f64; INTERLEAVE] {
+    let blocks = group.as_chunks::<BLOCK>().0;
+
+    let mut lanes = [[0.0; STEP]; INTERLEAVE];
+    for step in 0..BLOCK / STEP {
+        for (lanes, block) in lanes.iter_mut().zip(blocks) {
+            let chunk = &block[step * STEP..][..STEP];
+            let ahead = chunk.as_ptr().cast::<u8>().wrapping_add(distance);
+            for line in 0..LINES_PER_STEP {
+                prefetch::<HINT>(ahead.wrapping_add(line * LINE));
+            }
+            for (lane, x) in lanes.iter_mut().zip(chunk) {
+                *lane += x;
+            }
+        }
+    }
+
+    // The reduction of `chunked_sum_512_to_1`.
+    lanes.map(|s| sum_8_to_1(&core::array::from_fn(|i| s[i] + s[i + 8])))
+}
+
+#[cfg(target_arch = "x86_64")]
+const TEMPORAL: i32 = core::arch::x86_64::_MM_HINT_T0;
+#[cfg(target_arch = "x86_64")]
+const NON_TEMPORAL: i32 = core::arch::x86_64::_MM_HINT_NTA;
+#[cfg(not(target_arch = "x86_64"))]
+const TEMPORAL: i32 = 0;
+#[cfg(not(target_arch = "x86_64"))]
+const NON_TEMPORAL: i32 = 1;
+
+// A prefetch is only a hint: it never faults, so the address may lie past the
+// end of the input.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn prefetch<const HINT: i32>(address: *const u8) {
+    // SAFETY: `prefetch` (SSE) is part of the x86_64 baseline and does not
+    // dereference the address.
+    unsafe { core::arch::x86_64::_mm_prefetch::<HINT>(address.cast::<i8>()) }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+#[inline(always)]
+fn prefetch<const HINT: i32>(_address: *const u8) {}
@@ -0,0 +1,114 @@
+use crate::{BLOCK, ldexp};
+
+const EXPONENT_MASK: u64 = 0x7ff << 52;
+const ONE_EXPONENT: u64 = 1023 << 52;
+
+pub fn product(values: &[f64]) -> f64 {
+    let (mantissa, exponent) = product_exp(values);
+    ldexp(mantissa, exponent)
+}
+
+// Returns `(m, e)` with the product equal to `m * 2^e` and `1 <= |m| < 2`, so
+// the result cannot overflow or underflow however many values are multiplied.
+// Zero, infinite and NaN products come back as `(product, 0)`.
+//
+// Every value is split into its mantissa and exponent on the fly; the 16 lanes
+// multiply mantissas and add exponents, and are renormalised once per block.
+// Zeros, subnormals, infinities and NaN take a scalar path instead.
+pub fn product_exp(values: &[f64]) -> (f64, i64) {
+    let mut m = [1.0; 16];
+    let mut e = [0; 16];
+
+    let (blocks, remainder) = values.as_chunks::<BLOCK>();
+    for block in blocks {
+        if product_reduce_512(block, &mut m, &mut e) {
+            return product_exp_scalar(values);
+        }
+        for (m, e) in m.iter_mut().zip(&mut e) {
+            let (mantissa, exponent) = split(*m);
+            *m = mantissa;
+            *e += exponent;
+        }
+    }
+
+    let mut mantissa = 1.0;
+    let mut exponent = 0;
+    for (m, e) in m.into_iter().zip(e) {
+        let (m, me) = split(mantissa * m);
+        mantissa = m;
+        exponent += me + e;
+    }
+
+    for &x in remainder {
+        if !is_normal_bits(x.to_bits()) {
+            return product_exp_scalar(values);
+        }
+        let (m, me) = split(x);
+        let (m, mme) = split(mantissa * m);
+        mantissa = m;
+        exponent += me + mme;
+    }
+
+    (mantissa, exponent)
+}
+
+// Returns whether any value was zero, subnormal, infinite or NaN.
+fn product_reduce_512(values: &[f64; 512], m: &mut [f64; 16], e: &mut [i64; 16]) -> bool {
+    let mut special = 0;
+
+    let (chunks, _remainder) = values.as_chunks::<16>();
+    for chunk in chunks {
+        for ((m, e), &x) in m.iter_mut().zip(e.iter_mut()).zip(chunk) {
+            let bits = x.to_bits();
+            let field = bits & EXPONENT_MASK;
+            special |= (field == 0 || field == EXPONENT_MASK) as u8;
+            *m *= f64::from_bits((bits & !EXPONENT_MASK) | ONE_EXPONENT);
+            *e += (field >> 52) as i64 - 1023;
+        }
+    }
+
+    special != 0
+}
+
+fn product_exp_scalar(values: &[f64]) -> (f64, i64) {
+    let mut mantissa: f64 = 1.0;
+    let mut exponent = 0;
+    let mut special: f64 = 1.0;
+
+    for &x in values {
+        if x == 0.0 || !x.is_finite() {
+            special *= x;
+            continue;
+        }
+        let (m, me) = split(x);
+        let (m, mme) = split(mantissa * m);
+        mantissa = m;
+        exponent += me + mme;
+    }
+
+    if special != 1.0 {
+        return (special * mantissa, 0);
+    }
+    (mantissa, exponent)
+}
+
+#[inline(always)]
+fn is_normal_bits(bits: u64) -> bool {
+    let field = bits & EXPONENT_MASK;
+    field != 0 && field != EXPONENT_MASK
+}
+
+// Splits a finite non-zero `x` into `m * 2^e` with `1 <= |m| < 2`.
+#[inline(always)]
+fn split(x: f64) -> (f64, i64) {
+    let (x, bias) = if is_normal_bits(x.to_bits()) {
+        (x, 0)
+    } else {
+        (x * f64::from_bits((1023 + 64) << 52), 64)
+    };
+    let bits = x.to_bits();
+    (
+        f64::from_bits((bits & !EXPONENT_MASK) | ONE_EXPONENT),
+        ((bits & EXPONENT_MASK) >> 52) as i64 - 1023 - bias,
+    )
+}
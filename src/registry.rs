@@ -0,0 +1,618 @@
+use alloc::string::String;
+use core::any::Any;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{
+    Decimal, NanPolicy, argmax, argmin, bf16_sum_f32, bf16_sum_f64, chunked_sum, complex_dd_sum,
+    complex_fold_sum, complex_fold_sum_f32, complex_sum, complex_sum_f32, dd_sum, dd_sum_f64,
+    decimal_sum, decimal_sum_f64, exact_sum, expanded_fold_sum, expanded_fold_sum_with_scratch,
+    f16_sum_f32, f16_sum_f64, fold_sum, fold_sum_with_scratch, for_sum, iter_sum, l1_norm,
+    linf_norm, max, min, min_max, prefetch_fold_sum, product, product_exp, reproducible_sum,
+    stack_fold_sum, sum_gather, sum_strided, weighted_sum, wide_sum_fold0, wide_sum_fold1,
+    wide_sum_fold2,
+};
+#[cfg(feature = "std")]
+use crate::{l2_norm, p_norm, weighted_sum_eft, weighted_sum_fma};
+
+// How the worst-case error of a kernel grows with the length n of the input,
+// in units of the accumulator precision u. For a sum the error is relative to
+// sum |x|, for a product to the product itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Accuracy {
+    // One running sum: about n * u.
+    Recursive,
+    // Independent SIMD lanes: about (n / lanes) * u.
+    Lanes,
+    // Blocks of BLOCK folded into a tree: grows with log_BLOCK(n), not n.
+    Blocked,
+    // Double-double accumulation: about u + n * u^2.
+    Compensated,
+    // Exact accumulation, rounded once.
+    CorrectlyRounded,
+    // Not rounded at all: a decimal, a string of digits, or one of the values.
+    Exact,
+}
+
+// What a kernel computes, for the callers that treat sums differently from
+// the other reductions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Sum,
+    Norm,
+    Product,
+    // The smallest or largest value, or its index.
+    Extremum,
+}
+
+// The arguments a kernel takes, by the name `sums kernels` lists them under.
+// `Func` is the function pointer type of a kernel returning `S`.
+pub trait Input: 'static {
+    type Args<'a>;
+    type Func<S: 'static>: Copy + Sync + fmt::Debug + 'static;
+    const NAME: &'static str;
+
+    fn call<S: 'static>(func: Self::Func<S>, args: Self::Args<'_>) -> S;
+}
+
+impl Input for f64 {
+    type Args<'a> = &'a [f64];
+    type Func<S: 'static> = fn(&[f64]) -> S;
+    const NAME: &'static str = "f64";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, values: &[f64]) -> S {
+        func(values)
+    }
+}
+
+// f16 or bf16 bit patterns; the kernel's name says which.
+impl Input for u16 {
+    type Args<'a> = &'a [u16];
+    type Func<S: 'static> = fn(&[u16]) -> S;
+    const NAME: &'static str = "half";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, values: &[u16]) -> S {
+        func(values)
+    }
+}
+
+impl Input for Decimal {
+    type Args<'a> = &'a [Decimal];
+    type Func<S: 'static> = fn(&[Decimal]) -> S;
+    const NAME: &'static str = "Decimal";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, values: &[Decimal]) -> S {
+        func(values)
+    }
+}
+
+// Complex values interleaved as [re0, im0, re1, im1, ...].
+#[derive(Clone, Copy, Debug)]
+pub struct Interleaved<T>(PhantomData<T>);
+
+impl Input for Interleaved<f64> {
+    type Args<'a> = &'a [f64];
+    type Func<S: 'static> = fn(&[f64]) -> S;
+    const NAME: &'static str = "complex f64";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, values: &[f64]) -> S {
+        func(values)
+    }
+}
+
+impl Input for Interleaved<f32> {
+    type Args<'a> = &'a [f32];
+    type Func<S: 'static> = fn(&[f32]) -> S;
+    const NAME: &'static str = "complex f32";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, values: &[f32]) -> S {
+        func(values)
+    }
+}
+
+// Values and weights of the same length.
+#[derive(Clone, Copy, Debug)]
+pub struct Weighted;
+
+impl Input for Weighted {
+    type Args<'a> = (&'a [f64], &'a [f64]);
+    type Func<S: 'static> = fn(&[f64], &[f64]) -> S;
+    const NAME: &'static str = "f64, weights";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, weights): (&[f64], &[f64])) -> S {
+        func(values, weights)
+    }
+}
+
+// Every stride-th value.
+#[derive(Clone, Copy, Debug)]
+pub struct Strided;
+
+impl Input for Strided {
+    type Args<'a> = (&'a [f64], usize);
+    type Func<S: 'static> = fn(&[f64], usize) -> S;
+    const NAME: &'static str = "f64, stride";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, stride): (&[f64], usize)) -> S {
+        func(values, stride)
+    }
+}
+
+// The values at the given indices.
+#[derive(Clone, Copy, Debug)]
+pub struct Gathered;
+
+impl Input for Gathered {
+    type Args<'a> = (&'a [f64], &'a [usize]);
+    type Func<S: 'static> = fn(&[f64], &[usize]) -> S;
+    const NAME: &'static str = "f64, indices";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, indices): (&[f64], &[usize])) -> S {
+        func(values, indices)
+    }
+}
+
+// Values and a scratch buffer of at least `fold_sum_scratch_len` elements.
+#[derive(Clone, Copy, Debug)]
+pub struct Scratch;
+
+impl Input for Scratch {
+    type Args<'a> = (&'a [f64], &'a mut [f64]);
+    type Func<S: 'static> = fn(&[f64], &mut [f64]) -> S;
+    const NAME: &'static str = "f64, scratch";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, scratch): (&[f64], &mut [f64])) -> S {
+        func(values, scratch)
+    }
+}
+
+// Values and the exponent p of a norm.
+#[derive(Clone, Copy, Debug)]
+pub struct Power;
+
+impl Input for Power {
+    type Args<'a> = (&'a [f64], f64);
+    type Func<S: 'static> = fn(&[f64], f64) -> S;
+    const NAME: &'static str = "f64, p";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, p): (&[f64], f64)) -> S {
+        func(values, p)
+    }
+}
+
+// Values and the number of decimal places to round them to.
+#[derive(Clone, Copy, Debug)]
+pub struct AtScale;
+
+impl Input for AtScale {
+    type Args<'a> = (&'a [f64], u32);
+    type Func<S: 'static> = fn(&[f64], u32) -> S;
+    const NAME: &'static str = "f64, scale";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, scale): (&[f64], u32)) -> S {
+        func(values, scale)
+    }
+}
+
+// Values and what to do with NaN.
+#[derive(Clone, Copy, Debug)]
+pub struct WithNanPolicy;
+
+impl Input for WithNanPolicy {
+    type Args<'a> = (&'a [f64], NanPolicy);
+    type Func<S: 'static> = fn(&[f64], NanPolicy) -> S;
+    const NAME: &'static str = "f64, NanPolicy";
+
+    #[inline]
+    fn call<S: 'static>(func: Self::Func<S>, (values, nan): (&[f64], NanPolicy)) -> S {
+        func(values, nan)
+    }
+}
+
+// A registered kernel without its types, as `REGISTRY` holds it. `downcast`
+// recovers the `Kernel` to call it.
+pub trait Entry: Sync {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn accuracy(&self) -> Accuracy;
+    fn operation(&self) -> Operation;
+    // The name of the kernel's `Input`.
+    fn input(&self) -> &'static str;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl dyn Entry {
+    pub fn downcast<I: Input, S: 'static>(&self) -> Option<&Kernel<I, S>> {
+        self.as_any().downcast_ref()
+    }
+}
+
+impl fmt::Debug for dyn Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("name", &self.name())
+            .field("input", &self.input())
+            .finish_non_exhaustive()
+    }
+}
+
+// A kernel over `I` returning `S`, called through the `Input`.
+pub trait Summer<I: Input>: Entry {
+    type Output;
+
+    fn sum(&self, args: I::Args<'_>) -> Self::Output;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Kernel<I: Input, S: 'static = f64> {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub accuracy: Accuracy,
+    pub operation: Operation,
+    pub func: I::Func<S>,
+}
+
+impl<I: Input, S: 'static> Entry for Kernel<I, S> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn accuracy(&self) -> Accuracy {
+        self.accuracy
+    }
+
+    fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    fn input(&self) -> &'static str {
+        I::NAME
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<I: Input, S: 'static> Summer<I> for Kernel<I, S> {
+    type Output = S;
+
+    #[inline]
+    fn sum(&self, args: I::Args<'_>) -> S {
+        I::call(self.func, args)
+    }
+}
+
+// Every kernel of the crate, registered once. The binary, the benchmarks and
+// the fuzz targets iterate over this, or over `kernels` of one type.
+pub static REGISTRY: &[&dyn Entry] = &[
+    &Kernel::<f64> {
+        name: "for_sum",
+        description: "indexed loop, one accumulator",
+        accuracy: Accuracy::Recursive,
+        operation: Operation::Sum,
+        func: for_sum,
+    },
+    &Kernel::<f64> {
+        name: "iter_sum",
+        description: "`Iterator::sum`, one accumulator",
+        accuracy: Accuracy::Recursive,
+        operation: Operation::Sum,
+        func: iter_sum,
+    },
+    &Kernel::<f64> {
+        name: "fold_sum",
+        description: "16 lanes per 512-element block, blocks folded into a tree",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: fold_sum,
+    },
+    &Kernel::<f64> {
+        name: "stack_fold_sum",
+        description: "`fold_sum` with the partials on the stack, streaming",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: stack_fold_sum,
+    },
+    &Kernel::<f64> {
+        name: "chunked_sum",
+        description: "16 lanes over the whole input",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: chunked_sum,
+    },
+    &Kernel::<f64> {
+        name: "dd_sum_f64",
+        description: "16 double-double lanes",
+        accuracy: Accuracy::Compensated,
+        operation: Operation::Sum,
+        func: dd_sum_f64,
+    },
+    &Kernel::<f64> {
+        name: "wide_sum_fold0",
+        description: "16 lanes, folded sequentially",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: wide_sum_fold0,
+    },
+    &Kernel::<f64> {
+        name: "wide_sum_fold1",
+        description: "16 lanes, folded to 8 then sequentially",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: wide_sum_fold1,
+    },
+    &Kernel::<f64> {
+        name: "wide_sum_fold2",
+        description: "16 lanes, folded to 4 then sequentially",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: wide_sum_fold2,
+    },
+    &Kernel::<f64> {
+        name: "expanded_fold_sum",
+        description: "`fold_sum` with the 512-element blocks fully unrolled",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: expanded_fold_sum,
+    },
+    &Kernel::<f64> {
+        name: "prefetch_fold_sum",
+        description: "`fold_sum` over 4 interleaved blocks with software prefetch",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: prefetch_fold_sum,
+    },
+    &Kernel::<f64> {
+        name: "reproducible_sum",
+        description: "exact integer bins, order-independent",
+        accuracy: Accuracy::CorrectlyRounded,
+        operation: Operation::Sum,
+        func: reproducible_sum,
+    },
+    &Kernel::<f64, (f64, f64)> {
+        name: "dd_sum",
+        description: "`dd_sum_f64` with the low part of the double-double",
+        accuracy: Accuracy::Compensated,
+        operation: Operation::Sum,
+        func: dd_sum,
+    },
+    &Kernel::<Scratch> {
+        name: "fold_sum_with_scratch",
+        description: "`fold_sum` with the partials in a caller's buffer",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: fold_sum_with_scratch,
+    },
+    &Kernel::<Scratch> {
+        name: "expanded_fold_sum_with_scratch",
+        description: "`expanded_fold_sum` with the partials in a caller's buffer",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: expanded_fold_sum_with_scratch,
+    },
+    &Kernel::<Strided> {
+        name: "sum_strided",
+        description: "every stride-th value, 16 scalar loads into 16 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: sum_strided,
+    },
+    &Kernel::<Gathered> {
+        name: "sum_gather",
+        description: "the values at the indices, 16 bounds-checked loads into 16 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: sum_gather,
+    },
+    &Kernel::<Weighted> {
+        name: "weighted_sum",
+        description: "products in 16 lanes per block, blocks folded into a tree",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: weighted_sum,
+    },
+    #[cfg(feature = "std")]
+    &Kernel::<Weighted> {
+        name: "weighted_sum_fma",
+        description: "`weighted_sum` with fused multiply-adds",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: weighted_sum_fma,
+    },
+    #[cfg(feature = "std")]
+    &Kernel::<Weighted> {
+        name: "weighted_sum_eft",
+        description: "Dot2: products and adds with their exact errors",
+        accuracy: Accuracy::Compensated,
+        operation: Operation::Sum,
+        func: weighted_sum_eft,
+    },
+    &Kernel::<Interleaved<f64>, (f64, f64)> {
+        name: "complex_sum",
+        description: "8 lanes per part over the whole input",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: complex_sum,
+    },
+    &Kernel::<Interleaved<f64>, (f64, f64)> {
+        name: "complex_fold_sum",
+        description: "`fold_sum` of each part",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: complex_fold_sum,
+    },
+    &Kernel::<Interleaved<f64>, (f64, f64)> {
+        name: "complex_dd_sum",
+        description: "8 double-double lanes per part",
+        accuracy: Accuracy::Compensated,
+        operation: Operation::Sum,
+        func: complex_dd_sum,
+    },
+    &Kernel::<Interleaved<f32>, (f32, f32)> {
+        name: "complex_sum_f32",
+        description: "8 f32 lanes per part over the whole input",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: complex_sum_f32,
+    },
+    &Kernel::<Interleaved<f32>, (f32, f32)> {
+        name: "complex_fold_sum_f32",
+        description: "`complex_sum_f32` per block, blocks folded into a tree",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Sum,
+        func: complex_fold_sum_f32,
+    },
+    &Kernel::<u16, f32> {
+        name: "f16_sum_f32",
+        description: "f16 converted to 16 f32 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: f16_sum_f32,
+    },
+    &Kernel::<u16, f32> {
+        name: "bf16_sum_f32",
+        description: "bf16 converted to 16 f32 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: bf16_sum_f32,
+    },
+    &Kernel::<u16, f64> {
+        name: "f16_sum_f64",
+        description: "f16 converted to 16 f64 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: f16_sum_f64,
+    },
+    &Kernel::<u16, f64> {
+        name: "bf16_sum_f64",
+        description: "bf16 converted to 16 f64 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Sum,
+        func: bf16_sum_f64,
+    },
+    &Kernel::<Decimal, Option<Decimal>> {
+        name: "decimal_sum",
+        description: "i128 fixed point at the largest scale, `None` on overflow",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Sum,
+        func: decimal_sum,
+    },
+    &Kernel::<AtScale, Option<Decimal>> {
+        name: "decimal_sum_f64",
+        description: "values rounded to the scale, then added in i128 fixed point",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Sum,
+        func: decimal_sum_f64,
+    },
+    &Kernel::<f64, String> {
+        name: "exact_sum",
+        description: "exact integer bins, every decimal digit of the sum",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Sum,
+        func: exact_sum,
+    },
+    &Kernel::<f64> {
+        name: "l1_norm",
+        description: "`fold_sum` of |x|",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Norm,
+        func: l1_norm,
+    },
+    #[cfg(feature = "std")]
+    &Kernel::<f64> {
+        name: "l2_norm",
+        description: "`fold_sum` of squares scaled by the largest |x|, overflow-safe",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Norm,
+        func: l2_norm,
+    },
+    &Kernel::<f64> {
+        name: "linf_norm",
+        description: "largest |x| in 16 lanes, NaN propagates",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Norm,
+        func: linf_norm,
+    },
+    #[cfg(feature = "std")]
+    &Kernel::<Power> {
+        name: "p_norm",
+        description: "`fold_sum` of |x|^p scaled by the largest |x|",
+        accuracy: Accuracy::Blocked,
+        operation: Operation::Norm,
+        func: p_norm,
+    },
+    &Kernel::<f64> {
+        name: "product",
+        description: "`product_exp` scaled back, saturating to zero or infinity",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Product,
+        func: product,
+    },
+    &Kernel::<f64, (f64, i64)> {
+        name: "product_exp",
+        description: "mantissas multiplied and exponents added in 16 lanes",
+        accuracy: Accuracy::Lanes,
+        operation: Operation::Product,
+        func: product_exp,
+    },
+    &Kernel::<WithNanPolicy, Option<f64>> {
+        name: "min",
+        description: "smallest value in 16 lanes",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Extremum,
+        func: min,
+    },
+    &Kernel::<WithNanPolicy, Option<f64>> {
+        name: "max",
+        description: "largest value in 16 lanes",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Extremum,
+        func: max,
+    },
+    &Kernel::<WithNanPolicy, Option<(f64, f64)>> {
+        name: "min_max",
+        description: "smallest and largest value in one pass",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Extremum,
+        func: min_max,
+    },
+    &Kernel::<WithNanPolicy, Option<usize>> {
+        name: "argmin",
+        description: "index of the first smallest value",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Extremum,
+        func: argmin,
+    },
+    &Kernel::<WithNanPolicy, Option<usize>> {
+        name: "argmax",
+        description: "index of the first largest value",
+        accuracy: Accuracy::Exact,
+        operation: Operation::Extremum,
+        func: argmax,
+    },
+];
+
+pub fn kernel(name: &str) -> Option<&'static dyn Entry> {
+    REGISTRY.iter().copied().find(|entry| entry.name() == name)
+}
+
+// The registered kernels over `I` returning `S`, in registry order.
+pub fn kernels<I: Input, S: 'static>() -> impl Iterator<Item = &'static Kernel<I, S>> {
+    REGISTRY.iter().filter_map(|entry| entry.downcast())
+}
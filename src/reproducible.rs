@@ -0,0 +1,255 @@
+use crate::ldexp;
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec::Vec};
+
+// Each bin covers 32 binary exponents, starting at 2^-1074, so bin boundaries
+// are fixed and do not depend on the data. The last bins leave room for
+// carries above the largest finite value.
+const BIN_BITS: u32 = 32;
+const BINS: usize = 2048 / BIN_BITS as usize + 2;
+
+// A bin receives at most 85-bit terms, so an `i128` can take 2^42 of them
+// before it could overflow.
+const ADDS_BEFORE_CARRY: u64 = 1 << 40;
+
+// Binned accumulation in the style of ReproBLAS, with integer bins: every
+// value is split at fixed exponent boundaries and added exactly. The bins hold
+// the exact sum, so the result is rounded once and is bitwise identical for
+// any order of the values, any chunking through `add_slice` and any tree of
+// `merge`s, e.g. one accumulator per thread.
+#[derive(Clone, Debug)]
+pub struct ReproducibleSum {
+    bins: [i128; BINS],
+    adds: u64,
+    nan: bool,
+    pos_inf: bool,
+    neg_inf: bool,
+}
+
+impl Default for ReproducibleSum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReproducibleSum {
+    pub fn new() -> Self {
+        Self {
+            bins: [0; BINS],
+            adds: 0,
+            nan: false,
+            pos_inf: false,
+            neg_inf: false,
+        }
+    }
+
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        let bits = x.to_bits();
+        let field = ((bits >> 52) & 0x7ff) as u32;
+        let fraction = bits & ((1 << 52) - 1);
+
+        if field == 0x7ff {
+            if fraction != 0 {
+                self.nan = true;
+            } else if x > 0.0 {
+                self.pos_inf = true;
+            } else {
+                self.neg_inf = true;
+            }
+            return;
+        }
+
+        // x = significand * 2^(offset - 1074)
+        let (significand, offset) = if field == 0 {
+            (fraction, 0)
+        } else {
+            (fraction | (1 << 52), field - 1)
+        };
+        let term = ((significand as i128) << (offset % BIN_BITS)) * if x < 0.0 { -1 } else { 1 };
+        self.bins[(offset / BIN_BITS) as usize] += term;
+
+        self.adds += 1;
+        if self.adds == ADDS_BEFORE_CARRY {
+            self.carry();
+        }
+    }
+
+    pub fn add_slice(&mut self, values: &[f64]) {
+        for &x in values {
+            self.add(x);
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.carry();
+        let mut other = other.clone();
+        other.carry();
+
+        for (bin, other) in self.bins.iter_mut().zip(other.bins) {
+            *bin += other;
+        }
+        self.nan |= other.nan;
+        self.pos_inf |= other.pos_inf;
+        self.neg_inf |= other.neg_inf;
+        self.carry();
+    }
+
+    pub fn sum(&self) -> f64 {
+        if self.nan || (self.pos_inf && self.neg_inf) {
+            return f64::NAN;
+        }
+        if self.pos_inf {
+            return f64::INFINITY;
+        }
+        if self.neg_inf {
+            return f64::NEG_INFINITY;
+        }
+
+        let (negative, bins) = self.magnitude();
+        // The last bin is the only one not limited to 32 bits, and at 2^32 or
+        // more it alone is far beyond the largest finite value.
+        if bins[BINS - 1] >> BIN_BITS != 0 {
+            return if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            };
+        }
+        let Some(top) = bins.iter().rposition(|&bin| bin != 0) else {
+            return 0.0;
+        };
+
+        // Take the top four digits into a 128-bit window; anything below only
+        // matters as a sticky bit for rounding.
+        let mut window: u128 = 0;
+        for i in (top.saturating_sub(3)..=top).rev() {
+            window = (window << BIN_BITS) | bins[i] as u128;
+        }
+        let window_digits = (top + 1).min(4);
+        window <<= BIN_BITS as usize * (4 - window_digits);
+        let sticky = bins[..top + 1 - window_digits].iter().any(|&bin| bin != 0);
+
+        // A subnormal sum has fewer than 53 bits: round at 2^-1074 instead.
+        let subnormal_shift = (3 * BIN_BITS).saturating_sub(top as u32 * BIN_BITS);
+        let shift = (128 - window.leading_zeros() - 53).max(subnormal_shift);
+        let mut mantissa = (window >> shift) as u64;
+        let rest = window & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        if rest > half || (rest == half && (sticky || mantissa & 1 == 1)) {
+            mantissa += 1;
+        }
+
+        let exponent = (top as i64 - 3) * BIN_BITS as i64 + shift as i64 - 1074;
+        let sum = ldexp(mantissa as f64, exponent);
+        if negative { -sum } else { sum }
+    }
+
+    // The exact sum as a decimal string, without rounding: a sum of doubles is
+    // a multiple of 2^-1074, so it always has a finite decimal expansion.
+    // Non-finite sums give "NaN", "inf" or "-inf".
+    #[cfg(feature = "alloc")]
+    pub fn exact_string(&self) -> String {
+        if self.nan || (self.pos_inf && self.neg_inf) {
+            return String::from("NaN");
+        }
+        if self.pos_inf {
+            return String::from("inf");
+        }
+        if self.neg_inf {
+            return String::from("-inf");
+        }
+
+        // The magnitude is B * 2^-1074 = B * 5^1074 * 10^-1074, so B * 5^1074
+        // is built in base-10^9 limbs and the point is placed 1074 digits from
+        // the right.
+        let (negative, bins) = self.magnitude();
+        let mut limbs: Vec<u32> = Vec::new();
+        for &bin in bins.iter().rev() {
+            mul_add_limbs(&mut limbs, 1 << BIN_BITS, bin as u64);
+        }
+        for _ in 0..1074 / 13 {
+            mul_add_limbs(&mut limbs, 5u64.pow(13), 0);
+        }
+        mul_add_limbs(&mut limbs, 5u64.pow(1074 % 13), 0);
+
+        let mut digits = String::new();
+        if let Some((top, rest)) = limbs.split_last() {
+            digits = format!("{top}");
+            for limb in rest.iter().rev() {
+                digits.push_str(&format!("{limb:09}"));
+            }
+        }
+        if digits.len() <= 1074 {
+            digits = format!("{:0>1075}", digits);
+        }
+
+        let (integer, fraction) = digits.split_at(digits.len() - 1074);
+        let fraction = fraction.trim_end_matches('0');
+        let sign = if negative { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{sign}{integer}")
+        } else {
+            format!("{sign}{integer}.{fraction}")
+        }
+    }
+
+    // Sign and magnitude of the exact sum, with every bin in 0..2^32.
+    fn magnitude(&self) -> (bool, [i128; BINS]) {
+        let mut bins = self.bins;
+        carry(&mut bins);
+
+        // After the carry every bin but the last is in 0..2^32, and the last
+        // holds the sign.
+        let negative = bins[BINS - 1] < 0;
+        if negative {
+            for bin in &mut bins {
+                *bin = -*bin;
+            }
+            carry(&mut bins);
+        }
+        (negative, bins)
+    }
+
+    fn carry(&mut self) {
+        carry(&mut self.bins);
+        self.adds = 0;
+    }
+}
+
+fn carry(bins: &mut [i128; BINS]) {
+    for i in 0..BINS - 1 {
+        let carry = bins[i] >> BIN_BITS;
+        bins[i] -= carry << BIN_BITS;
+        bins[i + 1] += carry;
+    }
+}
+
+// limbs = limbs * factor + addend, in little-endian base-10^9 limbs.
+#[cfg(feature = "alloc")]
+fn mul_add_limbs(limbs: &mut Vec<u32>, factor: u64, addend: u64) {
+    let mut carry = addend;
+    for limb in limbs.iter_mut() {
+        let value = *limb as u64 * factor + carry;
+        *limb = (value % 1_000_000_000) as u32;
+        carry = value / 1_000_000_000;
+    }
+    while carry > 0 {
+        limbs.push((carry % 1_000_000_000) as u32);
+        carry /= 1_000_000_000;
+    }
+}
+
+pub fn reproducible_sum(values: &[f64]) -> f64 {
+    let mut sum = ReproducibleSum::new();
+    sum.add_slice(values);
+    sum.sum()
+}
+
+// The exact sum of `values` as a decimal string; see `ReproducibleSum::exact_string`.
+#[cfg(feature = "alloc")]
+pub fn exact_sum(values: &[f64]) -> String {
+    let mut sum = ReproducibleSum::new();
+    sum.add_slice(values);
+    sum.exact_string()
+}
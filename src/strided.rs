@@ -0,0 +1,156 @@
+use crate::{chunked_sum, sum_8_to_1};
+
+// Sums `values[0]`, `values[stride]`, `values[2 * stride]`, ...; panics if
+// `stride` is 0. A stride of 1 is contiguous and goes through `chunked_sum`.
+// Any other stride keeps the 16 independent accumulators of `chunked_reduce`,
+// but the loads are scalar, so only the adds overlap. The last partial group
+// of fewer than 16 elements is added sequentially, and so is the whole input
+// when a group of 16 strides does not even fit in the address space.
+pub fn sum_strided(values: &[f64], stride: usize) -> f64 {
+    assert!(stride > 0, "stride must be non-zero");
+    if stride == 1 {
+        return chunked_sum(values);
+    }
+    if stride.checked_mul(16).is_none() {
+        return values.iter().step_by(stride).sum();
+    }
+
+    let (remainder, mut s) = strided_reduce(values, stride);
+    s[0] += remainder.iter().step_by(stride).sum::<f64>();
+    sum_8_to_1(&s)
+}
+
+// Sums `values[i]` for every `i` in `indices`; panics if an index is out of
+// bounds. The indices are consumed 16 at a time into 16 independent
+// accumulators with bounds-checked scalar loads, and the last partial group of
+// fewer than 16 indices is added sequentially.
+pub fn sum_gather(values: &[f64], indices: &[usize]) -> f64 {
+    let (remainder, mut s) = gather_reduce(values, indices);
+    s[0] += remainder.iter().map(|&i| values[i]).sum::<f64>();
+    sum_8_to_1(&s)
+}
+
+// Requires 16 * stride not to overflow.
+fn strided_reduce(values: &[f64], stride: usize) -> (&[f64], [f64; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let mut chunks = values.chunks_exact(16 * stride);
+    for chunk in &mut chunks {
+        s0 += chunk[0];
+        s1 += chunk[stride];
+        s2 += chunk[2 * stride];
+        s3 += chunk[3 * stride];
+        s4 += chunk[4 * stride];
+        s5 += chunk[5 * stride];
+        s6 += chunk[6 * stride];
+        s7 += chunk[7 * stride];
+        s8 += chunk[8 * stride];
+        s9 += chunk[9 * stride];
+        s10 += chunk[10 * stride];
+        s11 += chunk[11 * stride];
+        s12 += chunk[12 * stride];
+        s13 += chunk[13 * stride];
+        s14 += chunk[14 * stride];
+        s15 += chunk[15 * stride];
+    }
+
+    (
+        chunks.remainder(),
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
+
+fn gather_reduce<'a>(values: &[f64], indices: &'a [usize]) -> (&'a [usize], [f64; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, remainder) = indices.as_chunks::<16>();
+    for &[
+        i0,
+        i1,
+        i2,
+        i3,
+        i4,
+        i5,
+        i6,
+        i7,
+        i8,
+        i9,
+        i10,
+        i11,
+        i12,
+        i13,
+        i14,
+        i15,
+    ] in chunks
+    {
+        s0 += values[i0];
+        s1 += values[i1];
+        s2 += values[i2];
+        s3 += values[i3];
+        s4 += values[i4];
+        s5 += values[i5];
+        s6 += values[i6];
+        s7 += values[i7];
+        s8 += values[i8];
+        s9 += values[i9];
+        s10 += values[i10];
+        s11 += values[i11];
+        s12 += values[i12];
+        s13 += values[i13];
+        s14 += values[i14];
+        s15 += values[i15];
+    }
+
+    (
+        remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
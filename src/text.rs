@@ -0,0 +1,170 @@
+use std::format;
+use std::io::{self, BufRead, ErrorKind};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{BLOCK, FoldSum, NanPolicy, max, min, min_max};
+
+const BUFFER_VALUES: usize = 8 * BLOCK;
+
+// How a line is split into columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delimiter {
+    // Every occurrence of the byte separates two fields, so `a,,b` has three.
+    Byte(u8),
+    // Runs of ASCII whitespace separate fields, and leading or trailing
+    // whitespace is ignored, as in awk's default field splitting.
+    Whitespace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextOptions {
+    // 0-based field to read from each line; `None` takes the whole line.
+    pub column: Option<usize>,
+    pub delimiter: Delimiter,
+    pub header: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            column: None,
+            delimiter: Delimiter::Byte(b','),
+            header: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextSummary {
+    pub count: u64,
+    pub sum: f64,
+    // NaN if any value was NaN; `None` without values.
+    pub min_max: Option<(f64, f64)>,
+}
+
+impl TextSummary {
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+// Sums numbers read from text, one per line or one field per line, across any
+// number of readers. Blank lines are skipped. Values are parsed with
+// `str::parse`, which uses the Eisel-Lemire fast path, into a buffer that is
+// handed to the kernels a block at a time, so the sum equals `fold_sum` over
+// all values.
+#[derive(Clone, Debug)]
+pub struct TextSum {
+    options: TextOptions,
+    sum: FoldSum,
+    count: u64,
+    min_max: Option<(f64, f64)>,
+    values: Vec<f64>,
+}
+
+impl TextSum {
+    pub fn new(options: TextOptions) -> Self {
+        Self {
+            options,
+            sum: FoldSum::new(),
+            count: 0,
+            min_max: None,
+            values: Vec::with_capacity(BUFFER_VALUES),
+        }
+    }
+
+    pub fn read(&mut self, mut reader: impl BufRead) -> io::Result<()> {
+        let mut line = Vec::new();
+        let mut line_number = 0;
+
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            line_number += 1;
+            if (self.options.header && line_number == 1) || line.trim_ascii().is_empty() {
+                continue;
+            }
+
+            let field = match self.options.column {
+                Some(column) => {
+                    nth_field(&line, self.options.delimiter, column).ok_or_else(|| {
+                        invalid_data(format!("line {line_number}: no column {}", column + 1))
+                    })?
+                }
+                None => &line[..],
+            };
+            let field = field.trim_ascii();
+            let field = field
+                .strip_prefix(b"\"")
+                .and_then(|f| f.strip_suffix(b"\""))
+                .unwrap_or(field);
+            if field.is_empty() && self.options.column.is_none() {
+                continue;
+            }
+
+            let value = core::str::from_utf8(field)
+                .ok()
+                .and_then(|f| f.parse::<f64>().ok())
+                .ok_or_else(|| {
+                    invalid_data(format!(
+                        "line {line_number}: cannot parse `{}` as a number",
+                        String::from_utf8_lossy(field)
+                    ))
+                })?;
+
+            self.values.push(value);
+            if self.values.len() == BUFFER_VALUES {
+                self.flush();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn summary(&mut self) -> TextSummary {
+        self.flush();
+        TextSummary {
+            count: self.count,
+            sum: self.sum.sum(),
+            min_max: self.min_max,
+        }
+    }
+
+    fn flush(&mut self) {
+        self.sum.add_slice(&self.values);
+        self.count += self.values.len() as u64;
+        if let Some((lo, hi)) = min_max(&self.values, NanPolicy::Propagate) {
+            self.min_max = Some(match self.min_max {
+                Some((min_so_far, max_so_far)) => (
+                    min(&[min_so_far, lo], NanPolicy::Propagate).unwrap(),
+                    max(&[max_so_far, hi], NanPolicy::Propagate).unwrap(),
+                ),
+                None => (lo, hi),
+            });
+        }
+        self.values.clear();
+    }
+}
+
+pub fn sum_text(reader: impl BufRead, options: TextOptions) -> io::Result<TextSummary> {
+    let mut sum = TextSum::new(options);
+    sum.read(reader)?;
+    Ok(sum.summary())
+}
+
+fn nth_field(line: &[u8], delimiter: Delimiter, n: usize) -> Option<&[u8]> {
+    match delimiter {
+        Delimiter::Byte(delimiter) => line.split(|&b| b == delimiter).nth(n),
+        Delimiter::Whitespace => line
+            .split(u8::is_ascii_whitespace)
+            .filter(|field| !field.is_empty())
+            .nth(n),
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
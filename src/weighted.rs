@@ -0,0 +1,626 @@
+#[cfg(feature = "std")]
+use crate::double_double::non_finite_sum;
+use crate::{BLOCK, Vec, fold_partials, fold_sum, sum_8_to_1};
+#[cfg(feature = "std")]
+use crate::{dd_sum_f64, two_product, two_sum};
+
+pub fn weighted_sum(values: &[f64], weights: &[f64]) -> f64 {
+    assert_eq!(values.len(), weights.len());
+
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return weighted_chunked_sum(values, weights);
+    }
+
+    let capacity_current = len.div_ceil(BLOCK);
+    let mut buffer_current = Vec::with_capacity(capacity_current);
+
+    let (chunks, remainder) = values.as_chunks::<BLOCK>();
+    let (weight_chunks, weight_remainder) = weights.as_chunks::<BLOCK>();
+    buffer_current.extend(
+        chunks
+            .iter()
+            .zip(weight_chunks)
+            .map(|(chunk, weight_chunk)| weighted_sum_512_to_1(chunk, weight_chunk)),
+    );
+
+    if !remainder.is_empty() {
+        buffer_current.push(weighted_chunked_sum(remainder, weight_remainder));
+    }
+
+    fold_partials(buffer_current)
+}
+
+#[cfg(feature = "std")]
+pub fn weighted_sum_fma(values: &[f64], weights: &[f64]) -> f64 {
+    assert_eq!(values.len(), weights.len());
+
+    let len = values.len();
+    if len < BLOCK * BLOCK / 2 {
+        return weighted_chunked_sum_fma(values, weights);
+    }
+
+    let capacity_current = len.div_ceil(BLOCK);
+    let mut buffer_current = Vec::with_capacity(capacity_current);
+
+    let (chunks, remainder) = values.as_chunks::<BLOCK>();
+    let (weight_chunks, weight_remainder) = weights.as_chunks::<BLOCK>();
+    buffer_current.extend(
+        chunks
+            .iter()
+            .zip(weight_chunks)
+            .map(|(chunk, weight_chunk)| weighted_sum_fma_512_to_1(chunk, weight_chunk)),
+    );
+
+    if !remainder.is_empty() {
+        buffer_current.push(weighted_chunked_sum_fma(remainder, weight_remainder));
+    }
+
+    fold_partials(buffer_current)
+}
+
+// Dot2 (Ogita, Rump, Oishi): every product and every lane update is split into
+// its rounded result and exact error, and the errors are summed separately.
+// The result is as accurate as if computed in twice the working precision, so
+// no block fold is needed.
+#[cfg(feature = "std")]
+pub fn weighted_sum_eft(values: &[f64], weights: &[f64]) -> f64 {
+    assert_eq!(values.len(), weights.len());
+
+    let (remainder, weight_remainder, mut s, mut c) = weighted_reduce_eft(values, weights);
+    for (&x, &w) in remainder.iter().zip(weight_remainder) {
+        dot2_step(&mut s[0], &mut c[0], x, w);
+    }
+    if let Some(sum) = non_finite_sum(s.into_iter()) {
+        return sum;
+    }
+
+    let mut total = 0.0;
+    let mut error = 0.0;
+    for (s, c) in s.into_iter().zip(c) {
+        let (t, e) = two_sum(total, s);
+        total = t;
+        error += e + c;
+    }
+    // Finite lanes whose sum overflows leave NaN in the error.
+    if !total.is_finite() {
+        return total;
+    }
+    total + error
+}
+
+pub fn weighted_mean(values: &[f64], weights: &[f64]) -> f64 {
+    weighted_sum(values, weights) / fold_sum(weights)
+}
+
+#[cfg(feature = "std")]
+pub fn weighted_mean_fma(values: &[f64], weights: &[f64]) -> f64 {
+    weighted_sum_fma(values, weights) / fold_sum(weights)
+}
+
+// The weights are summed in double-double too, so that cancellation among
+// them does not undo the accuracy of the numerator.
+#[cfg(feature = "std")]
+pub fn weighted_mean_eft(values: &[f64], weights: &[f64]) -> f64 {
+    weighted_sum_eft(values, weights) / dd_sum_f64(weights)
+}
+
+#[inline]
+fn weighted_chunked_sum(values: &[f64], weights: &[f64]) -> f64 {
+    let (remainder, weight_remainder, mut s) = weighted_reduce(values, weights);
+    for (&x, &w) in remainder.iter().zip(weight_remainder) {
+        s[0] += x * w;
+    }
+    sum_8_to_1(&s)
+}
+
+fn weighted_reduce<'a>(values: &'a [f64], weights: &'a [f64]) -> (&'a [f64], &'a [f64], [f64; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    let (weight_chunks, weight_remainder) = weights.as_chunks::<16>();
+    for (
+        &[
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ],
+        &[
+            w0,
+            w1,
+            w2,
+            w3,
+            w4,
+            w5,
+            w6,
+            w7,
+            w8,
+            w9,
+            w10,
+            w11,
+            w12,
+            w13,
+            w14,
+            w15,
+        ],
+    ) in chunks.iter().zip(weight_chunks)
+    {
+        s0 += c0 * w0;
+        s1 += c1 * w1;
+        s2 += c2 * w2;
+        s3 += c3 * w3;
+        s4 += c4 * w4;
+        s5 += c5 * w5;
+        s6 += c6 * w6;
+        s7 += c7 * w7;
+        s8 += c8 * w8;
+        s9 += c9 * w9;
+        s10 += c10 * w10;
+        s11 += c11 * w11;
+        s12 += c12 * w12;
+        s13 += c13 * w13;
+        s14 += c14 * w14;
+        s15 += c15 * w15;
+    }
+
+    (
+        remainder,
+        weight_remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
+
+#[inline]
+fn weighted_sum_512_to_1(values: &[f64; 512], weights: &[f64; 512]) -> f64 {
+    let values = weighted_reduce_512_to_8(values, weights);
+    sum_8_to_1(&values)
+}
+
+fn weighted_reduce_512_to_8(values: &[f64; 512], weights: &[f64; 512]) -> [f64; 8] {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, _remainder) = values.as_chunks::<16>();
+    let (weight_chunks, _weight_remainder) = weights.as_chunks::<16>();
+    for (
+        &[
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ],
+        &[
+            w0,
+            w1,
+            w2,
+            w3,
+            w4,
+            w5,
+            w6,
+            w7,
+            w8,
+            w9,
+            w10,
+            w11,
+            w12,
+            w13,
+            w14,
+            w15,
+        ],
+    ) in chunks.iter().zip(weight_chunks)
+    {
+        s0 += c0 * w0;
+        s1 += c1 * w1;
+        s2 += c2 * w2;
+        s3 += c3 * w3;
+        s4 += c4 * w4;
+        s5 += c5 * w5;
+        s6 += c6 * w6;
+        s7 += c7 * w7;
+        s8 += c8 * w8;
+        s9 += c9 * w9;
+        s10 += c10 * w10;
+        s11 += c11 * w11;
+        s12 += c12 * w12;
+        s13 += c13 * w13;
+        s14 += c14 * w14;
+        s15 += c15 * w15;
+    }
+
+    [
+        s0 + s8,
+        s1 + s9,
+        s2 + s10,
+        s3 + s11,
+        s4 + s12,
+        s5 + s13,
+        s6 + s14,
+        s7 + s15,
+    ]
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn weighted_chunked_sum_fma(values: &[f64], weights: &[f64]) -> f64 {
+    let (remainder, weight_remainder, mut s) = weighted_reduce_fma(values, weights);
+    for (&x, &w) in remainder.iter().zip(weight_remainder) {
+        s[0] = x.mul_add(w, s[0]);
+    }
+    sum_8_to_1(&s)
+}
+
+#[cfg(feature = "std")]
+fn weighted_reduce_fma<'a>(
+    values: &'a [f64],
+    weights: &'a [f64],
+) -> (&'a [f64], &'a [f64], [f64; 8]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    let (weight_chunks, weight_remainder) = weights.as_chunks::<16>();
+    for (
+        &[
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ],
+        &[
+            w0,
+            w1,
+            w2,
+            w3,
+            w4,
+            w5,
+            w6,
+            w7,
+            w8,
+            w9,
+            w10,
+            w11,
+            w12,
+            w13,
+            w14,
+            w15,
+        ],
+    ) in chunks.iter().zip(weight_chunks)
+    {
+        s0 = c0.mul_add(w0, s0);
+        s1 = c1.mul_add(w1, s1);
+        s2 = c2.mul_add(w2, s2);
+        s3 = c3.mul_add(w3, s3);
+        s4 = c4.mul_add(w4, s4);
+        s5 = c5.mul_add(w5, s5);
+        s6 = c6.mul_add(w6, s6);
+        s7 = c7.mul_add(w7, s7);
+        s8 = c8.mul_add(w8, s8);
+        s9 = c9.mul_add(w9, s9);
+        s10 = c10.mul_add(w10, s10);
+        s11 = c11.mul_add(w11, s11);
+        s12 = c12.mul_add(w12, s12);
+        s13 = c13.mul_add(w13, s13);
+        s14 = c14.mul_add(w14, s14);
+        s15 = c15.mul_add(w15, s15);
+    }
+
+    (
+        remainder,
+        weight_remainder,
+        [
+            s0 + s8,
+            s1 + s9,
+            s2 + s10,
+            s3 + s11,
+            s4 + s12,
+            s5 + s13,
+            s6 + s14,
+            s7 + s15,
+        ],
+    )
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn weighted_sum_fma_512_to_1(values: &[f64; 512], weights: &[f64; 512]) -> f64 {
+    let values = weighted_reduce_fma_512_to_8(values, weights);
+    sum_8_to_1(&values)
+}
+
+#[cfg(feature = "std")]
+fn weighted_reduce_fma_512_to_8(values: &[f64; 512], weights: &[f64; 512]) -> [f64; 8] {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+
+    let (chunks, _remainder) = values.as_chunks::<16>();
+    let (weight_chunks, _weight_remainder) = weights.as_chunks::<16>();
+    for (
+        &[
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ],
+        &[
+            w0,
+            w1,
+            w2,
+            w3,
+            w4,
+            w5,
+            w6,
+            w7,
+            w8,
+            w9,
+            w10,
+            w11,
+            w12,
+            w13,
+            w14,
+            w15,
+        ],
+    ) in chunks.iter().zip(weight_chunks)
+    {
+        s0 = c0.mul_add(w0, s0);
+        s1 = c1.mul_add(w1, s1);
+        s2 = c2.mul_add(w2, s2);
+        s3 = c3.mul_add(w3, s3);
+        s4 = c4.mul_add(w4, s4);
+        s5 = c5.mul_add(w5, s5);
+        s6 = c6.mul_add(w6, s6);
+        s7 = c7.mul_add(w7, s7);
+        s8 = c8.mul_add(w8, s8);
+        s9 = c9.mul_add(w9, s9);
+        s10 = c10.mul_add(w10, s10);
+        s11 = c11.mul_add(w11, s11);
+        s12 = c12.mul_add(w12, s12);
+        s13 = c13.mul_add(w13, s13);
+        s14 = c14.mul_add(w14, s14);
+        s15 = c15.mul_add(w15, s15);
+    }
+
+    [
+        s0 + s8,
+        s1 + s9,
+        s2 + s10,
+        s3 + s11,
+        s4 + s12,
+        s5 + s13,
+        s6 + s14,
+        s7 + s15,
+    ]
+}
+
+#[cfg(feature = "std")]
+#[inline(always)]
+fn dot2_step(s: &mut f64, c: &mut f64, x: f64, w: f64) {
+    let (p, q) = two_product(x, w);
+    let (t, e) = two_sum(*s, p);
+    *s = t;
+    *c += q + e;
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::type_complexity)]
+fn weighted_reduce_eft<'a>(
+    values: &'a [f64],
+    weights: &'a [f64],
+) -> (&'a [f64], &'a [f64], [f64; 16], [f64; 16]) {
+    let mut s0 = 0.0;
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    let mut s3 = 0.0;
+    let mut s4 = 0.0;
+    let mut s5 = 0.0;
+    let mut s6 = 0.0;
+    let mut s7 = 0.0;
+    let mut s8 = 0.0;
+    let mut s9 = 0.0;
+    let mut s10 = 0.0;
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s13 = 0.0;
+    let mut s14 = 0.0;
+    let mut s15 = 0.0;
+    let mut e0 = 0.0;
+    let mut e1 = 0.0;
+    let mut e2 = 0.0;
+    let mut e3 = 0.0;
+    let mut e4 = 0.0;
+    let mut e5 = 0.0;
+    let mut e6 = 0.0;
+    let mut e7 = 0.0;
+    let mut e8 = 0.0;
+    let mut e9 = 0.0;
+    let mut e10 = 0.0;
+    let mut e11 = 0.0;
+    let mut e12 = 0.0;
+    let mut e13 = 0.0;
+    let mut e14 = 0.0;
+    let mut e15 = 0.0;
+
+    let (chunks, remainder) = values.as_chunks::<16>();
+    let (weight_chunks, weight_remainder) = weights.as_chunks::<16>();
+    for (
+        &[
+            c0,
+            c1,
+            c2,
+            c3,
+            c4,
+            c5,
+            c6,
+            c7,
+            c8,
+            c9,
+            c10,
+            c11,
+            c12,
+            c13,
+            c14,
+            c15,
+        ],
+        &[
+            w0,
+            w1,
+            w2,
+            w3,
+            w4,
+            w5,
+            w6,
+            w7,
+            w8,
+            w9,
+            w10,
+            w11,
+            w12,
+            w13,
+            w14,
+            w15,
+        ],
+    ) in chunks.iter().zip(weight_chunks)
+    {
+        dot2_step(&mut s0, &mut e0, c0, w0);
+        dot2_step(&mut s1, &mut e1, c1, w1);
+        dot2_step(&mut s2, &mut e2, c2, w2);
+        dot2_step(&mut s3, &mut e3, c3, w3);
+        dot2_step(&mut s4, &mut e4, c4, w4);
+        dot2_step(&mut s5, &mut e5, c5, w5);
+        dot2_step(&mut s6, &mut e6, c6, w6);
+        dot2_step(&mut s7, &mut e7, c7, w7);
+        dot2_step(&mut s8, &mut e8, c8, w8);
+        dot2_step(&mut s9, &mut e9, c9, w9);
+        dot2_step(&mut s10, &mut e10, c10, w10);
+        dot2_step(&mut s11, &mut e11, c11, w11);
+        dot2_step(&mut s12, &mut e12, c12, w12);
+        dot2_step(&mut s13, &mut e13, c13, w13);
+        dot2_step(&mut s14, &mut e14, c14, w14);
+        dot2_step(&mut s15, &mut e15, c15, w15);
+    }
+
+    (
+        remainder,
+        weight_remainder,
+        [
+            s0, s1, s2, s3, s4, s5, s6, s7, s8, s9, s10, s11, s12, s13, s14, s15,
+        ],
+        [
+            e0, e1, e2, e3, e4, e5, e6, e7, e8, e9, e10, e11, e12, e13, e14, e15,
+        ],
+    )
+}
@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::OnceLock;
+
+// A directory holding a file named like a subcommand and one with an
+// extension, both with the f64 values 1.5 and 2.5; written once, as the tests
+// run in parallel.
+fn files() -> &'static Path {
+    static FILES: OnceLock<PathBuf> = OnceLock::new();
+    FILES.get_or_init(|| {
+        let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli");
+        fs::create_dir_all(&dir).unwrap();
+        let bytes: Vec<u8> = [1.5f64, 2.5].iter().flat_map(|x| x.to_le_bytes()).collect();
+        fs::write(dir.join("kernels"), &bytes).unwrap();
+        fs::write(dir.join("values.bin"), &bytes).unwrap();
+        dir
+    })
+}
+
+fn sums(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sums"))
+        .args(args)
+        .current_dir(files())
+        .output()
+        .expect("failed to run sums")
+}
+
+fn stdout(output: &Output) -> &str {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    std::str::from_utf8(&output.stdout).unwrap().trim()
+}
+
+#[test]
+fn file_named_like_a_subcommand() {
+    assert_eq!(stdout(&sums(&["file", "kernels"])), "4");
+    assert_eq!(stdout(&sums(&["--", "kernels"])), "4");
+    assert_eq!(
+        stdout(&sums(&["file", "--dtype", "f64", "--", "kernels"])),
+        "4"
+    );
+    assert_eq!(stdout(&sums(&["./kernels"])), "4");
+    // Without `file`, it is the subcommand.
+    assert!(stdout(&sums(&["kernels"])).contains("fold_sum"));
+}
+
+#[test]
+fn paths_are_summed_without_file() {
+    assert_eq!(stdout(&sums(&["values.bin"])), "4");
+    assert_eq!(stdout(&sums(&["values.bin", "--dtype", "f64"])), "4");
+    assert_eq!(stdout(&sums(&["--dtype", "f64", "values.bin"])), "4");
+}
+
+#[test]
+fn unknown_subcommands_are_errors() {
+    let output = sums(&["kernles"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown subcommand `kernles`"), "{stderr}");
+    assert!(stderr.contains("kernels, asm"), "{stderr}");
+    assert!(stderr.contains("sums file kernles"), "{stderr}");
+
+    let output = sums(&["values.bin", "--dtyp", "f32"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown option `--dtyp`"));
+}
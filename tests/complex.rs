@@ -0,0 +1,22 @@
+use sums::{complex_dd_sum, complex_sum};
+
+#[test]
+fn complex_dd_sum_overflows_to_infinity_when_lanes_combine() {
+    let mut values = [0.0; 16];
+    values[0] = f64::MAX;
+    values[2] = f64::MAX;
+    values[1] = 1.0;
+    values[3] = 1.0;
+    assert_eq!(complex_sum(&values), (f64::INFINITY, 2.0));
+    assert_eq!(complex_dd_sum(&values), (f64::INFINITY, 2.0));
+
+    values.swap(0, 1);
+    values.swap(2, 3);
+    assert_eq!(complex_dd_sum(&values), (2.0, f64::INFINITY));
+}
+
+#[test]
+fn complex_dd_sum_keeps_the_parts_apart() {
+    let values = [1e16, 1.0, 1.0, -1e16, -1e16, 2.0, 1.0, 1e16].repeat(3);
+    assert_eq!(complex_dd_sum(&values), (6.0, 9.0));
+}
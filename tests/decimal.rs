@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use sums::{AtScale, Decimal, Summer, kernel};
+
+#[test]
+fn decimals_compare_by_value() {
+    assert_eq!(Decimal::new(10, 1), Decimal::new(100, 2));
+    assert_eq!(Decimal::new(0, 0), Decimal::new(0, 7));
+    assert_eq!(Decimal::new(-5, 0), Decimal::new(-500, 2));
+    assert_ne!(Decimal::new(10, 1), Decimal::new(10, 2));
+
+    let set: HashSet<Decimal> = [Decimal::new(10, 1), Decimal::new(100, 2)].into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn decimal_kernels_are_registered() {
+    let values: Vec<Decimal> = ["0.10", "0.2", "-0.05"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    let decimal_sum = kernel("decimal_sum").unwrap();
+    let sum = decimal_sum
+        .downcast::<Decimal, Option<Decimal>>()
+        .unwrap()
+        .sum(&values);
+    assert_eq!(sum, Some(Decimal::new(25, 2)));
+
+    let decimal_sum_f64 = kernel("decimal_sum_f64").unwrap();
+    let decimal_sum_f64 = decimal_sum_f64
+        .downcast::<AtScale, Option<Decimal>>()
+        .unwrap();
+    let cents = decimal_sum_f64.sum((&[0.1, 0.2, 0.3], 2));
+    assert_eq!(cents.map(|sum| sum.to_string()), Some("0.60".to_string()));
+}
+
+#[test]
+fn from_f64_is_exact_at_large_scales() {
+    let units = |x: f64, scale: u32| Decimal::from_f64(x, scale).map(|d| d.units);
+    assert_eq!(units(0.1, 25), Some(1000000000000000055511151));
+    assert_eq!(units(1e-10, 45), Some(100000000000000003643219731549774158));
+    assert_eq!(
+        units(123456.789, 30),
+        Some(123456789000000004307366907596588135)
+    );
+    assert_eq!(
+        units(5e-324, 361),
+        Some(49406564584124654417656879286822137237)
+    );
+    assert_eq!(units(5e-324, 362), None);
+    assert_eq!(units(0.0, 1000), Some(0));
+    assert_eq!(units(1e38, 1), None);
+    assert_eq!(units(-(2f64.powi(100)), 0), Some(-(1 << 100)));
+
+    assert_eq!(
+        sums::decimal_sum_f64(&[0.1, 0.2], 25).map(|d| d.units),
+        Some(3000000000000000166533453)
+    );
+}
+
+#[test]
+fn from_f64_rounds_half_to_even() {
+    let units = |x: f64, scale: u32| Decimal::from_f64(x, scale).map(|d| d.units);
+    assert_eq!(units(0.125, 2), Some(12));
+    assert_eq!(units(0.375, 2), Some(38));
+    assert_eq!(units(-2.675, 2), Some(-267));
+    assert_eq!(units(2.5, 0), Some(2));
+    assert_eq!(units(1e-300, 2), Some(0));
+}
@@ -0,0 +1,27 @@
+use sums::{dd_sum, dd_sum_f64, fold_sum};
+
+#[test]
+fn dd_sum_overflows_to_infinity_when_lanes_combine() {
+    let mut values = [0.0; 16];
+    values[0] = f64::MAX;
+    values[1] = f64::MAX;
+    assert_eq!(fold_sum(&values), f64::INFINITY);
+    assert_eq!(dd_sum(&values), (f64::INFINITY, 0.0));
+
+    values[0] = -f64::MAX;
+    values[1] = -f64::MAX;
+    assert_eq!(dd_sum_f64(&values), f64::NEG_INFINITY);
+}
+
+#[test]
+fn dd_sum_propagates_infinities_and_nan() {
+    assert_eq!(dd_sum(&[1.0, f64::INFINITY, 2.0]), (f64::INFINITY, 0.0));
+    assert!(dd_sum_f64(&[f64::INFINITY, f64::NEG_INFINITY]).is_nan());
+    assert!(dd_sum_f64(&[1.0, f64::NAN]).is_nan());
+}
+
+#[test]
+fn dd_sum_recovers_cancelled_bits() {
+    let values = [1e16, 1.0, -1e16, 1.0].repeat(5);
+    assert_eq!(dd_sum(&values), (10.0, 0.0));
+}
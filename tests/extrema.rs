@@ -0,0 +1,102 @@
+use sums::{NanPolicy, argmax, argmin, max, min, min_max};
+
+const POLICIES: [NanPolicy; 2] = [NanPolicy::Propagate, NanPolicy::Ignore];
+
+fn same(a: Option<f64>, b: Option<f64>) -> bool {
+    a.map(f64::to_bits) == b.map(f64::to_bits)
+}
+
+#[test]
+fn empty_input_has_no_extremes() {
+    for nan in POLICIES {
+        assert_eq!(min(&[], nan), None);
+        assert_eq!(max(&[], nan), None);
+        assert_eq!(min_max(&[], nan), None);
+        assert_eq!(argmin(&[], nan), None);
+        assert_eq!(argmax(&[], nan), None);
+    }
+}
+
+#[test]
+fn arg_extremes_order_negative_zero_below_positive_zero() {
+    for values in [vec![0.0, -0.0], vec![-0.0, 0.0], [0.0; 40].to_vec()] {
+        let mut values = values;
+        if values.len() == 40 {
+            // In a different lane than the first +0.0, and in the remainder.
+            values[21] = -0.0;
+            values[37] = -0.0;
+        }
+        let at = |i: Option<usize>| i.map(|i| values[i]);
+        let nan = NanPolicy::Propagate;
+        assert!(
+            same(at(argmin(&values, nan)), min(&values, nan)),
+            "{values:?}"
+        );
+        assert!(
+            same(at(argmax(&values, nan)), max(&values, nan)),
+            "{values:?}"
+        );
+        assert!(at(argmin(&values, nan)).unwrap().is_sign_negative());
+        assert!(at(argmax(&values, nan)).unwrap().is_sign_positive());
+
+        let nan = NanPolicy::Ignore;
+        assert!(at(argmin(&values, nan)).unwrap().is_sign_negative());
+        assert!(at(argmax(&values, nan)).unwrap().is_sign_positive());
+        assert_eq!(at(argmin(&values, nan)), min(&values, nan));
+    }
+    assert_eq!(argmin(&[0.0, -0.0, -0.0], NanPolicy::Propagate), Some(1));
+    assert_eq!(argmax(&[-0.0, 0.0, 0.0], NanPolicy::Propagate), Some(1));
+}
+
+#[test]
+fn nan_propagates_or_is_skipped() {
+    let mut values: Vec<f64> = (0..50).map(|i| (i * 7 % 50) as f64).collect();
+    values[33] = f64::NAN;
+    values[45] = f64::NAN;
+
+    let nan = NanPolicy::Propagate;
+    assert!(min(&values, nan).unwrap().is_nan());
+    assert!(max(&values, nan).unwrap().is_nan());
+    let (lo, hi) = min_max(&values, nan).unwrap();
+    assert!(lo.is_nan() && hi.is_nan());
+    assert_eq!(argmin(&values, nan), Some(33));
+    assert_eq!(argmax(&values, nan), Some(33));
+
+    let nan = NanPolicy::Ignore;
+    assert_eq!(min(&values, nan), Some(0.0));
+    assert_eq!(max(&values, nan), Some(49.0));
+    assert_eq!(min_max(&values, nan), Some((0.0, 49.0)));
+    assert_eq!(argmin(&values, nan), Some(0));
+    assert_eq!(argmax(&values, nan), Some(7));
+}
+
+#[test]
+fn all_nan_input() {
+    let values = [f64::NAN; 20];
+    let nan = NanPolicy::Propagate;
+    assert!(min(&values, nan).unwrap().is_nan());
+    assert!(max(&values, nan).unwrap().is_nan());
+    assert_eq!(argmin(&values, nan), Some(0));
+    assert_eq!(argmax(&values, nan), Some(0));
+
+    let nan = NanPolicy::Ignore;
+    assert_eq!(min(&values, nan), None);
+    assert_eq!(max(&values, nan), None);
+    assert_eq!(min_max(&values, nan), None);
+    assert_eq!(argmin(&values, nan), None);
+    assert_eq!(argmax(&values, nan), None);
+}
+
+#[test]
+fn ties_go_to_the_lowest_index() {
+    let mut values = [1.0; 70];
+    values[5] = -3.0;
+    values[20] = -3.0;
+    values[66] = -3.0;
+    values[17] = 4.0;
+    values[3] = 4.0;
+    for nan in POLICIES {
+        assert_eq!(argmin(&values, nan), Some(5));
+        assert_eq!(argmax(&values, nan), Some(3));
+    }
+}
@@ -0,0 +1,97 @@
+use sums::{bf16_sum_f32, bf16_sum_f64, bf16_to_f32, f16_sum_f32, f16_sum_f64, f16_to_f32};
+
+// IEEE binary16 decoded by its definition.
+fn f16_value(x: u16) -> f64 {
+    let sign = if x & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((x >> 10) & 0x1f) as i32;
+    let mantissa = (x & 0x3ff) as f64;
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1024.0 + mantissa) * 2f64.powi(exponent - 25),
+    }
+}
+
+#[test]
+fn f16_to_f32_is_exact_for_every_value() {
+    for x in 0..=u16::MAX {
+        let (value, expected) = (f16_to_f32(x), f16_value(x));
+        if expected.is_nan() {
+            assert!(value.is_nan(), "{x:#06x}: {value}");
+            assert_eq!(value.is_sign_negative(), x & 0x8000 != 0, "{x:#06x}");
+        } else {
+            assert_eq!(value as f64, expected, "{x:#06x}");
+            assert_eq!(value.is_sign_negative(), x & 0x8000 != 0, "{x:#06x}");
+        }
+    }
+}
+
+#[test]
+fn f16_special_values() {
+    assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+    assert_eq!(f16_to_f32(0x03ff), 1023.0 * 2f32.powi(-24));
+    assert_eq!(f16_to_f32(0x0400), 2f32.powi(-14));
+    assert_eq!(f16_to_f32(0x3c00), 1.0);
+    assert_eq!(f16_to_f32(0x3c01), 1.0 + 2f32.powi(-10));
+    assert_eq!(f16_to_f32(0x7bff), 65504.0);
+    assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+    assert_eq!(f16_to_f32(0xfc00), f32::NEG_INFINITY);
+    assert_eq!(f16_to_f32(0x8000).to_bits(), (-0.0f32).to_bits());
+    assert!(f16_to_f32(0x7e00).is_nan());
+    // A signalling NaN keeps its payload bit.
+    assert_eq!(f16_to_f32(0x7c01).to_bits() & 0x3fff, 1 << 13);
+}
+
+#[test]
+fn bf16_to_f32_keeps_the_top_bits() {
+    assert_eq!(bf16_to_f32(0x3f80), 1.0);
+    assert_eq!(bf16_to_f32(0xc040), -3.0);
+    assert_eq!(bf16_to_f32(0x0001), f32::from_bits(0x0001_0000));
+    assert_eq!(bf16_to_f32(0x0080), f32::MIN_POSITIVE);
+    assert_eq!(bf16_to_f32(0x7f7f), f32::from_bits(0x7f7f_0000));
+    assert_eq!(bf16_to_f32(0x7f80), f32::INFINITY);
+    assert_eq!(bf16_to_f32(0xff80), f32::NEG_INFINITY);
+    assert!(bf16_to_f32(0x7fc0).is_nan());
+    for x in 0..=u16::MAX {
+        assert_eq!(bf16_to_f32(x).to_bits(), (x as u32) << 16);
+    }
+}
+
+// Blocks of 16 values are converted with F16C where the target has it, the
+// remainder with `f16_to_f32`; 16 copies of a value sum to exactly 16 times
+// it in either precision, so both paths must agree for every value.
+#[test]
+fn block_conversion_matches_f16_to_f32() {
+    for x in 0..=u16::MAX {
+        let (block, single) = ([x; 16], f16_to_f32(x));
+        let (sum_f32, sum_f64) = (f16_sum_f32(&block), f16_sum_f64(&block));
+        if single.is_nan() {
+            assert!(sum_f32.is_nan() && sum_f64.is_nan(), "{x:#06x}");
+            continue;
+        }
+        assert_eq!(sum_f32, 16.0 * single, "{x:#06x}");
+        assert_eq!(sum_f64, 16.0 * single as f64, "{x:#06x}");
+        assert_eq!(f16_sum_f32(&[x]), single + 0.0, "{x:#06x}");
+    }
+}
+
+#[test]
+fn sums_round_in_the_accumulator() {
+    // 2048 + 1 is exact in f32 but not in f16.
+    let (two_k, one) = (0x6800, 0x3c00);
+    assert_eq!(f16_sum_f32(&[two_k, one]), 2049.0);
+
+    // 512 * 32768 = 2^24, and 2^24 + 1 rounds to even in f32 but not in f64.
+    let mut values = vec![0x7800; 512];
+    values.push(one);
+    assert_eq!(f16_sum_f32(&values), 16_777_216.0);
+    assert_eq!(f16_sum_f64(&values), 16_777_217.0);
+
+    // bf16 has 8 bits of precision, the sums 24 and 53.
+    let (big, small) = (0x4b80, 0x3f80); // 2^24, 1
+    assert_eq!(bf16_sum_f32(&[big, small]), 16_777_216.0);
+    assert_eq!(bf16_sum_f64(&[big, small]), 16_777_217.0);
+    assert!(bf16_sum_f32(&[0x7f80, 0xff80]).is_nan());
+    assert_eq!(f16_sum_f64(&[0x7bff; 40]), 40.0 * 65504.0);
+}
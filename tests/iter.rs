@@ -0,0 +1,101 @@
+use sums::{BLOCK, FoldSum, SumExt, chunked_sum, fold_sum};
+
+// Mixed signs and magnitudes, so that a different order of adds shows.
+fn values(len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|i| (i.wrapping_mul(2_654_435_761) % 1_000_003) as f64 * 1e-3 - 500.0)
+        .collect()
+}
+
+// Every stream form of `values` must give `fold_sum`'s bits: the iterator,
+// one value at a time, and slices of uneven lengths mixed with single values.
+fn same_as_fold_sum(values: &[f64]) {
+    let expected = fold_sum(values).to_bits();
+    let len = values.len();
+    assert_eq!(
+        values.iter().copied().fold_sum().to_bits(),
+        expected,
+        "SumExt, {len} values"
+    );
+
+    let mut one_by_one = FoldSum::new();
+    for &x in values {
+        one_by_one.add(x);
+    }
+    assert_eq!(one_by_one.sum().to_bits(), expected, "add, {len} values");
+
+    let mut chunked = FoldSum::new();
+    let mut rest = values;
+    for size in [1, 7, 511, 512, 513, 3, 1000, 100_003].into_iter().cycle() {
+        if rest.is_empty() {
+            break;
+        }
+        let (head, tail) = rest.split_at(size.min(rest.len()));
+        if size == 3 {
+            head.iter().for_each(|&x| chunked.add(x));
+        } else {
+            chunked.add_slice(head);
+        }
+        rest = tail;
+    }
+    assert_eq!(chunked.sum().to_bits(), expected, "add_slice, {len} values");
+}
+
+#[test]
+fn streams_match_fold_sum_around_block() {
+    for len in [0, 1, 15, 16, 17, BLOCK - 1, BLOCK, BLOCK + 1, 2 * BLOCK + 5] {
+        same_as_fold_sum(&values(len));
+    }
+}
+
+#[test]
+fn streams_match_fold_sum_around_the_fold() {
+    let half = BLOCK * BLOCK / 2;
+    for len in [
+        half - 1,
+        half,
+        half + 1,
+        half + BLOCK,
+        BLOCK * BLOCK - 1,
+        BLOCK * BLOCK,
+    ] {
+        same_as_fold_sum(&values(len));
+    }
+    for len in [BLOCK * BLOCK + 1, 3 * BLOCK * BLOCK + 77] {
+        same_as_fold_sum(&values(len));
+    }
+}
+
+#[test]
+fn streams_match_fold_sum_past_two_levels() {
+    // BLOCK^3 / 2 values, where the partials themselves fold.
+    let half = BLOCK * BLOCK * BLOCK / 2;
+    same_as_fold_sum(&values(half + BLOCK + 1));
+}
+
+#[test]
+fn sum_can_be_taken_along_the_way() {
+    let values = values(BLOCK * BLOCK + 3);
+    let mut stream = FoldSum::new();
+    for (i, chunk) in values.chunks(40_000).enumerate() {
+        stream.add_slice(chunk);
+        let end = (i * 40_000 + chunk.len()).min(values.len());
+        assert_eq!(
+            stream.sum().to_bits(),
+            fold_sum(&values[..end]).to_bits(),
+            "{end}"
+        );
+    }
+}
+
+#[test]
+fn fast_sum_matches_chunked_sum() {
+    for len in [0, 1, 15, 16, 17, 1000] {
+        let values = values(len);
+        assert_eq!(
+            values.iter().copied().fast_sum().to_bits(),
+            chunked_sum(&values).to_bits(),
+            "{len} values"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use sums::{Layout, col_sums, fold_sum, row_sums};
+
+// 2 x 3:
+// 1 2 3
+// 4 5 6
+const ROW_MAJOR: [f64; 6] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+const COLUMN_MAJOR: [f64; 6] = [1.0, 4.0, 2.0, 5.0, 3.0, 6.0];
+
+#[test]
+fn row_major() {
+    assert_eq!(row_sums(&ROW_MAJOR, 2, 3, Layout::RowMajor), [6.0, 15.0]);
+    assert_eq!(
+        col_sums(&ROW_MAJOR, 2, 3, Layout::RowMajor),
+        [5.0, 7.0, 9.0]
+    );
+}
+
+#[test]
+fn column_major() {
+    assert_eq!(
+        row_sums(&COLUMN_MAJOR, 2, 3, Layout::ColumnMajor),
+        [6.0, 15.0]
+    );
+    assert_eq!(
+        col_sums(&COLUMN_MAJOR, 2, 3, Layout::ColumnMajor),
+        [5.0, 7.0, 9.0]
+    );
+}
+
+#[test]
+fn empty_matrices() {
+    for layout in [Layout::RowMajor, Layout::ColumnMajor] {
+        // 0 x 4: no rows, four empty columns.
+        assert_eq!(row_sums(&[], 0, 4, layout), []);
+        assert_eq!(col_sums(&[], 0, 4, layout), [0.0; 4]);
+        // 4 x 0: four empty rows, no columns.
+        assert_eq!(row_sums(&[], 4, 0, layout), [0.0; 4]);
+        assert_eq!(col_sums(&[], 4, 0, layout), []);
+    }
+}
+
+#[test]
+fn tall_matrices() {
+    // Enough rows to flush the row-major column accumulators through two
+    // levels; column-major columns are contiguous and go through `fold_sum`.
+    let (rows, cols) = (600 * 512, 3);
+    let values: Vec<f64> = (0..rows * cols).map(|i| (i % 7) as f64 - 2.9).collect();
+    let columns: Vec<Vec<f64>> = (0..cols)
+        .map(|c| values.iter().skip(c).step_by(cols).copied().collect())
+        .collect();
+    let transposed = columns.concat();
+
+    let by_rows = col_sums(&values, rows, cols, Layout::RowMajor);
+    let by_columns = col_sums(&transposed, rows, cols, Layout::ColumnMajor);
+    for ((a, b), column) in by_rows.iter().zip(&by_columns).zip(&columns) {
+        assert_eq!(b.to_bits(), fold_sum(column).to_bits());
+        assert!((a - b).abs() <= 1e-9 * b.abs(), "{a} {b}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "4611686018427387904 x 4 matrix")]
+fn overflowing_dimensions_panic() {
+    row_sums(&[], 1 << 62, 4, Layout::RowMajor);
+}
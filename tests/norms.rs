@@ -0,0 +1,128 @@
+use sums::{BLOCK, fold_sum, l1_norm, l2_norm, linf_norm, p_norm};
+
+#[test]
+fn p_norm_of_subnormal_values() {
+    let tiny = f64::from_bits(1);
+    assert_eq!(p_norm(&[tiny; 8], 1.5), 4.0 * tiny);
+    assert_eq!(p_norm(&[0.0, tiny, -tiny], 3.0), tiny);
+    assert_eq!(
+        p_norm(&[0.0, f64::MIN_POSITIVE / 2.0], 1.5),
+        f64::MIN_POSITIVE / 2.0
+    );
+}
+
+#[test]
+fn p_norm_with_large_p() {
+    let norm = p_norm(&[1.9, 1.5, -1.0], 2000.0);
+    assert!((norm - 1.9).abs() <= 4.0 * f64::EPSILON, "{norm}");
+    let norm = p_norm(&[f64::MAX, 1.0, -f64::MAX / 4.0], 3000.0);
+    assert!(
+        (norm / f64::MAX - 1.0).abs() <= 4.0 * f64::EPSILON,
+        "{norm:e}"
+    );
+}
+
+// Mixed signs and magnitudes, so that a different order of adds shows.
+fn values(len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|i| (i.wrapping_mul(2_654_435_761) % 1_000_003) as f64 * 1e-3 - 500.0)
+        .collect()
+}
+
+#[test]
+fn l1_norm_is_fold_sum_of_magnitudes() {
+    // Through the lanes, their remainder, and the blocks of the fold.
+    for len in [
+        0,
+        1,
+        15,
+        17,
+        1000,
+        BLOCK * BLOCK / 2 - 1,
+        BLOCK * BLOCK / 2,
+        300_001,
+    ] {
+        let values = values(len);
+        let magnitudes: Vec<f64> = values.iter().map(|x| x.abs()).collect();
+        assert_eq!(
+            l1_norm(&values).to_bits(),
+            fold_sum(&magnitudes).to_bits(),
+            "{len} values"
+        );
+        assert_eq!(p_norm(&values, 1.0).to_bits(), l1_norm(&values).to_bits());
+    }
+}
+
+#[test]
+fn l2_norm_is_fold_sum_of_scaled_squares() {
+    for len in [
+        1,
+        15,
+        17,
+        1000,
+        BLOCK * BLOCK / 2 - 1,
+        BLOCK * BLOCK / 2,
+        300_001,
+    ] {
+        let values = values(len);
+        // The largest |x| is below 512 = 2^9.
+        let squares: Vec<f64> = values.iter().map(|x| (x / 256.0) * (x / 256.0)).collect();
+        assert_eq!(
+            l2_norm(&values).to_bits(),
+            (fold_sum(&squares).sqrt() * 256.0).to_bits(),
+            "{len} values"
+        );
+        assert_eq!(p_norm(&values, 2.0).to_bits(), l2_norm(&values).to_bits());
+    }
+}
+
+#[test]
+fn l2_norm_does_not_overflow_or_underflow() {
+    assert_eq!(l2_norm(&[3e300, 4e300]), 5e300);
+    assert_eq!(l2_norm(&[-3e300, 0.0, 4e300]), 5e300);
+    assert_eq!(l2_norm(&[f64::MAX, 0.0]), f64::MAX);
+    assert_eq!(l2_norm(&[f64::MAX, f64::MAX]), f64::INFINITY);
+    let small = 2f64.powi(-1000);
+    assert_eq!(l2_norm(&[3.0 * small, 4.0 * small]), 5.0 * small);
+}
+
+#[test]
+fn norms_of_subnormal_values() {
+    let tiny = f64::from_bits(1);
+    assert_eq!(l2_norm(&[3.0 * tiny, -4.0 * tiny]), 5.0 * tiny);
+    assert_eq!(l2_norm(&[tiny; 4]), 2.0 * tiny);
+    assert_eq!(l1_norm(&[tiny, -tiny, 3.0 * tiny]), 5.0 * tiny);
+    assert_eq!(linf_norm(&[tiny, -3.0 * tiny, 2.0 * tiny]), 3.0 * tiny);
+    let half_min = f64::MIN_POSITIVE / 2.0;
+    assert_eq!(l2_norm(&[0.0, -half_min]), half_min);
+}
+
+#[test]
+fn norms_of_empty_and_zero_input() {
+    for norm in [l1_norm, l2_norm, linf_norm] {
+        assert_eq!(norm(&[]).to_bits(), 0.0f64.to_bits());
+        assert_eq!(norm(&[-0.0, 0.0]), 0.0);
+    }
+    assert_eq!(p_norm(&[], 3.0), 0.0);
+}
+
+#[test]
+fn linf_norm_propagates_nan() {
+    // NaN in every lane position, in the remainder, and next to infinities.
+    for len in [1, 16, 17, 40] {
+        for at in 0..len {
+            let mut values = values(len);
+            values[at] = f64::NAN;
+            assert!(linf_norm(&values).is_nan(), "NaN at {at} of {len}");
+            if len > 1 {
+                values[(at + 1) % len] = f64::INFINITY;
+                assert!(linf_norm(&values).is_nan(), "NaN and inf in {len}");
+            }
+        }
+    }
+    assert_eq!(linf_norm(&[1.0, f64::NEG_INFINITY, 2.0]), f64::INFINITY);
+    assert!(l1_norm(&[1.0, f64::NAN]).is_nan());
+    assert!(l2_norm(&[1.0, f64::NAN, f64::INFINITY]).is_nan());
+    assert!(p_norm(&[f64::NAN, 1.0], 3.0).is_nan());
+    assert_eq!(l2_norm(&[1.0, f64::NEG_INFINITY]), f64::INFINITY);
+}
@@ -0,0 +1,51 @@
+use sums::{log_sum_exp, product, product_exp};
+
+#[test]
+fn product_splits_large_values_before_multiplying() {
+    let (mantissa, exponent) = product_exp(&[1.5, f64::MAX]);
+    assert_eq!(mantissa, 1.5 * (2.0 - f64::EPSILON) / 2.0);
+    assert_eq!(exponent, 1024);
+
+    let p = product(&[f64::MAX, f64::MAX, 1e-300, 1e-300]);
+    let expected = (f64::MAX * 1e-300) * (f64::MAX * 1e-300);
+    assert!((p - expected).abs() <= 1e-15 * expected, "{p:e}");
+}
+
+#[test]
+fn product_keeps_small_values() {
+    let p = product(&[f64::MIN_POSITIVE, f64::MIN_POSITIVE, 1.5, 1e300, 1e300]);
+    let expected = (f64::MIN_POSITIVE * 1e300) * (f64::MIN_POSITIVE * 1e300) * 1.5;
+    assert!((p - expected).abs() <= 1e-15 * expected, "{p:e}");
+    assert_eq!(product_exp(&[f64::MIN_POSITIVE; 3]), (1.0, -3066));
+}
+
+#[test]
+fn product_across_blocks() {
+    // 1000 values: one full block of lanes and a remainder.
+    let values: Vec<f64> = (0..1000)
+        .map(|i| if i % 2 == 0 { 1e300 } else { 1e-300 })
+        .collect();
+    let p = product(&values);
+    assert!((p - 1.0).abs() < 1e-12, "{p}");
+    assert_eq!(product(&[2.0; 1100]), f64::INFINITY);
+    assert_eq!(product_exp(&[2.0; 1100]), (1.0, 1100));
+    assert_eq!(product_exp(&[]), (1.0, 0));
+}
+
+#[test]
+fn product_of_special_values() {
+    assert_eq!(product(&[1e300, 0.0, 1e300]), 0.0);
+    assert_eq!(product(&[-2.0, f64::INFINITY]), f64::NEG_INFINITY);
+    assert!(product(&[0.0, f64::INFINITY]).is_nan());
+    assert!(product(&[1.0, f64::NAN]).is_nan());
+    assert_eq!(product(&[5e-324, 2.0]), 1e-323);
+}
+
+#[test]
+fn log_sum_exp_does_not_overflow() {
+    assert_eq!(log_sum_exp(&[1000.0, 1000.0]), 1000.0 + 2f64.ln());
+    assert_eq!(log_sum_exp(&[-1000.0]), -1000.0);
+    assert_eq!(log_sum_exp(&[]), f64::NEG_INFINITY);
+    assert_eq!(log_sum_exp(&[1.0, f64::INFINITY]), f64::INFINITY);
+    assert!(log_sum_exp(&[1.0, f64::NAN]).is_nan());
+}
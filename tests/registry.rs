@@ -0,0 +1,41 @@
+use std::collections::HashSet;
+
+use sums::{Entry, REGISTRY, Summer, Weighted, fold_sum, kernel, kernels};
+
+#[test]
+fn every_registered_name_resolves() {
+    let mut names = HashSet::new();
+    for entry in REGISTRY {
+        assert!(
+            names.insert(entry.name()),
+            "{} is registered twice",
+            entry.name()
+        );
+        assert_eq!(
+            kernel(entry.name()).map(|found| found.name()),
+            Some(entry.name())
+        );
+    }
+    assert!(kernel("no_such_kernel").is_none());
+}
+
+#[test]
+fn kernels_downcast_to_their_types() {
+    let values = [1.0, 2.0, 3.5];
+    let fold = kernel("fold_sum").unwrap();
+    assert_eq!(
+        fold.downcast::<f64, f64>().unwrap().sum(&values),
+        fold_sum(&values)
+    );
+    assert!(fold.downcast::<f64, f32>().is_none());
+    assert!(fold.downcast::<Weighted, f64>().is_none());
+
+    for weighted in kernels::<Weighted, f64>() {
+        assert_eq!(
+            weighted.sum((&values, &[2.0; 3])),
+            13.0,
+            "{}",
+            weighted.name()
+        );
+    }
+}
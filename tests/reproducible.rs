@@ -0,0 +1,28 @@
+use sums::{ReproducibleSum, reproducible_sum};
+
+// Sums beyond the largest finite value overflow to infinity. An exact sum of
+// 2^1038 fills the last bin up to 2^32, which the fuzz targets cannot reach:
+// they only check inputs whose sum of |x| is finite.
+#[test]
+fn reproducible_sum_overflows_to_infinity() {
+    let max = 2f64.powi(1023);
+    assert_eq!(reproducible_sum(&vec![max; 32768]), f64::INFINITY);
+    assert_eq!(reproducible_sum(&vec![-max; 32768]), f64::NEG_INFINITY);
+    assert_eq!(reproducible_sum(&[f64::MAX, f64::MAX]), f64::INFINITY);
+
+    // Cancelling back into range still gives the exact result.
+    let mut sum = ReproducibleSum::new();
+    sum.add_slice(&vec![max; 32768]);
+    sum.add_slice(&vec![-max; 32767]);
+    assert_eq!(sum.sum(), max);
+}
+
+#[test]
+fn reproducible_sum_rounds_subnormals() {
+    let tiny = f64::from_bits(1);
+    assert_eq!(reproducible_sum(&[tiny; 8]), f64::from_bits(8));
+    assert_eq!(
+        reproducible_sum(&[f64::MIN_POSITIVE, -tiny]),
+        f64::from_bits(f64::MIN_POSITIVE.to_bits() - 1)
+    );
+}
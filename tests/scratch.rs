@@ -0,0 +1,57 @@
+use sums::{
+    BLOCK, expanded_fold_sum, expanded_fold_sum_with_scratch, fold_sum, fold_sum_scratch_len,
+    fold_sum_with_scratch,
+};
+
+// Mixed signs and magnitudes, so that a different order of adds shows.
+fn values(len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|i| (i.wrapping_mul(2_654_435_761) % 1_000_003) as f64 * 1e-3 - 500.0)
+        .collect()
+}
+
+fn same_as_fold_sum(values: &[f64]) {
+    let mut scratch = vec![0.0; fold_sum_scratch_len(values.len())];
+    assert_eq!(
+        fold_sum_with_scratch(values, &mut scratch).to_bits(),
+        fold_sum(values).to_bits(),
+        "{} values",
+        values.len()
+    );
+    assert_eq!(
+        expanded_fold_sum_with_scratch(values, &mut scratch).to_bits(),
+        expanded_fold_sum(values).to_bits(),
+        "{} values",
+        values.len()
+    );
+}
+
+#[test]
+fn scratch_sums_match_below_the_fold() {
+    for len in [0, 1, 511, 512, BLOCK * BLOCK / 2 - 1] {
+        same_as_fold_sum(&values(len));
+    }
+}
+
+#[test]
+fn scratch_sums_match_across_fold_levels() {
+    // Past BLOCK^3 / 2, so the partials are folded more than once.
+    for len in [
+        BLOCK * BLOCK / 2,
+        BLOCK * BLOCK / 2 + 1,
+        1_000_003,
+        67_121_209,
+    ] {
+        same_as_fold_sum(&values(len));
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "scratch too small: 262144 values need fold_sum_scratch_len = 513, got 512"
+)]
+fn short_scratch_panics() {
+    let values = values(BLOCK * BLOCK);
+    let mut scratch = vec![0.0; fold_sum_scratch_len(values.len()) - 1];
+    fold_sum_with_scratch(&values, &mut scratch);
+}
@@ -0,0 +1,17 @@
+use sums::sum_strided;
+
+#[test]
+fn huge_strides_take_the_first_value() {
+    assert_eq!(sum_strided(&[1.0, 2.0, 3.0], 1 << 60), 1.0);
+    assert_eq!(sum_strided(&[1.0, 2.0, 3.0], usize::MAX), 1.0);
+    assert_eq!(sum_strided(&[], usize::MAX), 0.0);
+}
+
+#[test]
+fn strides_match_a_sequential_sum() {
+    let values: Vec<f64> = (0..1000).map(f64::from).collect();
+    for stride in [2, 3, 7, 16, 63, 999, 1000, 5000] {
+        let expected: f64 = values.iter().step_by(stride).sum();
+        assert_eq!(sum_strided(&values, stride), expected, "stride {stride}");
+    }
+}
@@ -0,0 +1,31 @@
+use sums::{Delimiter, TextOptions, sum_text};
+
+fn sum(text: &str, options: TextOptions) -> (u64, f64) {
+    let summary = sum_text(text.as_bytes(), options).unwrap();
+    (summary.count, summary.sum)
+}
+
+#[test]
+fn blank_lines_are_skipped_with_a_column() {
+    let options = TextOptions {
+        column: Some(1),
+        ..TextOptions::default()
+    };
+    assert_eq!(sum("a,1\n\nb,2\n  \n", options), (2, 3.0));
+}
+
+#[test]
+fn whitespace_splits_on_runs() {
+    let options = TextOptions {
+        column: Some(0),
+        delimiter: Delimiter::Whitespace,
+        header: false,
+    };
+    assert_eq!(sum("  1 x\n\t2   y\n\n3.5\n", options), (3, 6.5));
+
+    let second = TextOptions {
+        column: Some(1),
+        ..options
+    };
+    assert!(sum_text("1 2\n3\n".as_bytes(), second).is_err());
+}
@@ -0,0 +1,28 @@
+use std::path::Path;
+use std::process::Command;
+
+// The kernels only vectorise in the optimised build, and with LTO only the
+// final binary's assembly shows the code that actually runs, so this builds
+// the release binary and runs `sums asm --check` on the assembly it emitted.
+#[test]
+fn kernels_vectorise() {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("vectorisation");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--release", "--bin", "sums", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "release build failed");
+
+    let output = Command::new(target_dir.join("release").join("sums"))
+        .args(["asm", "--check"])
+        .output()
+        .expect("failed to run sums");
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,32 @@
+use sums::{fold_sum, weighted_mean_eft, weighted_sum, weighted_sum_eft, weighted_sum_fma};
+
+#[test]
+fn weighted_mean_eft_compensates_the_weights() {
+    let values = [1.0; 4];
+    let weights = [1e16, 1.0, -1e16, 1.0];
+    assert_ne!(fold_sum(&weights), 2.0);
+    assert_eq!(weighted_mean_eft(&values, &weights), 1.0);
+}
+
+#[test]
+fn weighted_sum_eft_overflows_like_the_other_kernels() {
+    let mut values = [0.0; 16];
+    values[0] = f64::MAX;
+    values[1] = f64::MAX;
+    let weights = [1.0; 16];
+    for sum in [weighted_sum, weighted_sum_fma, weighted_sum_eft] {
+        assert_eq!(sum(&values, &weights), f64::INFINITY);
+    }
+
+    values[1] = 1e300;
+    let mut weights = [2.0; 16];
+    weights[0] = -1.0;
+    weights[1] = -1e10;
+    assert_eq!(weighted_sum_eft(&values, &weights), f64::NEG_INFINITY);
+
+    assert_eq!(
+        weighted_sum_eft(&[1.0, f64::INFINITY], &[1.0, 1.0]),
+        f64::INFINITY
+    );
+    assert!(weighted_sum_eft(&[0.0, 1.0], &[f64::INFINITY, 1.0]).is_nan());
+}